@@ -12,6 +12,11 @@ TABS="$ROOT/tabs.txt"
 FOX="$ROOT/fox.txt"
 SPIDERS="$ROOT/spiders.txt"
 BUSTLE="$ROOT/the-bustle.txt"
+BYTES="$ROOT/bytes.bin"
+LATIN1="$ROOT/latin1.txt"
+CRLF="$ROOT/crlf.txt"
+NO_NEWLINE="$ROOT/no-newline.txt"
+BLANKS="$ROOT/blanks.txt"
 ALL="$EMPTY $FOX $SPIDERS $BUSTLE"
 
 for FILE in $ALL; do
@@ -24,14 +29,51 @@ done
 
 cat -T $TABS > ${OUT_DIR}/$(basename $TABS).T.out
 cat -v $BUSTLE > ${OUT_DIR}/$(basename $BUSTLE).v.out
+cat -v $BYTES > ${OUT_DIR}/$(basename $BYTES).v.out
+cat -vT $BYTES > ${OUT_DIR}/$(basename $BYTES).vT.out
+
+for FILE in $TABS $BUSTLE $BYTES; do
+    BASENAME=$(basename "$FILE")
+    cat -A $FILE > ${OUT_DIR}/${BASENAME}.A.out
+    cat -e $FILE > ${OUT_DIR}/${BASENAME}.e.out
+    cat -t $FILE > ${OUT_DIR}/${BASENAME}.t.out
+done
+
+cat $BYTES > ${OUT_DIR}/$(basename $BYTES).out
+
+for FILE in $LATIN1 $CRLF $NO_NEWLINE; do
+    BASENAME=$(basename "$FILE")
+    cat    $FILE > ${OUT_DIR}/${BASENAME}.out
+    cat -n $FILE > ${OUT_DIR}/${BASENAME}.n.out
+    cat -b $FILE > ${OUT_DIR}/${BASENAME}.b.out
+    cat -E $FILE > ${OUT_DIR}/${BASENAME}.E.out
+    cat -A $FILE > ${OUT_DIR}/${BASENAME}.A.out
+done
+
+cat    $NO_NEWLINE $LATIN1 $CRLF > $OUT_DIR/joined.out
+cat -n $NO_NEWLINE $LATIN1 $CRLF > $OUT_DIR/joined.n.out
+cat -E $NO_NEWLINE $LATIN1 $CRLF > $OUT_DIR/joined.E.out
+
+BASENAME=$(basename "$BLANKS")
+cat -s   $BLANKS > ${OUT_DIR}/${BASENAME}.s.out
+cat -sn  $BLANKS > ${OUT_DIR}/${BASENAME}.sn.out
+cat -sb  $BLANKS > ${OUT_DIR}/${BASENAME}.sb.out
+cat -sE  $BLANKS > ${OUT_DIR}/${BASENAME}.sE.out
+cat -s < $BLANKS > ${OUT_DIR}/${BASENAME}.s.stdin.out
+
+cat -s  $BLANKS $BLANKS $NO_NEWLINE $BLANKS > $OUT_DIR/squeezed.s.out
+cat -sn $BLANKS $BLANKS $NO_NEWLINE $BLANKS > $OUT_DIR/squeezed.sn.out
 
 cat    $ALL > $OUT_DIR/all.out
 cat -n $ALL > $OUT_DIR/all.n.out
 cat -b $ALL > $OUT_DIR/all.b.out
 cat -E $ALL > $OUT_DIR/all.E.out
+cat -A $ALL > $OUT_DIR/all.A.out
+cat -s $ALL > $OUT_DIR/all.s.out
 
 cat    < $BUSTLE > $OUT_DIR/$(basename $BUSTLE).stdin.out
 cat -n < $BUSTLE > $OUT_DIR/$(basename $BUSTLE).n.stdin.out
 cat -b < $BUSTLE > $OUT_DIR/$(basename $BUSTLE).b.stdin.out
 cat -E < $BUSTLE > $OUT_DIR/$(basename $BUSTLE).E.stdin.out
+cat -A < $BUSTLE > $OUT_DIR/$(basename $BUSTLE).A.stdin.out
 
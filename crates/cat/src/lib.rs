@@ -1,10 +1,10 @@
 #![allow(dead_code)]
-use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, Write};
 
 use anyhow::Result;
 use clap::{Arg, ArgAction, Command};
 use indoc::indoc;
+use roarutils_core::{open, Reporter};
 
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
@@ -15,49 +15,182 @@ pub struct Config {
     show_ends: bool,
     show_nonprinting: bool,
     show_tabs: bool,
+    squeeze_blank: bool,
 }
 
+impl Config {
+    /// Whether any option requires inspecting the input line by line.
+    const fn is_formatted(&self) -> bool {
+        self.number_lines
+            || self.number_nonblank_lines
+            || self.show_ends
+            || self.show_nonprinting
+            || self.show_tabs
+            || self.squeeze_blank
+    }
+}
+
+/// Line numbering state, carried across files so that numbering continues,
+/// a final line without a newline is joined with the next file's first, and
+/// blank lines are squeezed across file boundaries.
+#[derive(Debug, Default)]
+struct LineState {
+    line_num: usize,
+    mid_line: bool,
+    prev_blank: bool,
+}
+
+/// Where copying a file to standard output failed. Read errors are reported
+/// against the file and skipped, write errors end the run.
+#[derive(Debug)]
+enum CopyError {
+    Read(io::Error),
+    Write(io::Error),
+}
+
+/// Returns the exit status: `0` if every file was read, `1` if any could not
+/// be opened or read. Such files are reported and skipped.
+///
 /// # Errors
 ///
-/// Will return `Err` if the file(s) fail to open.
-pub fn run(config: Config) -> Result<()> {
-    let mut line_num = 0;
-    for filename in config.files {
-        match open(&filename) {
-            Err(err) => eprintln!("Failed to open {filename}: {err}"),
-            Ok(file) => {
-                for line_result in file.lines() {
-                    let mut line = line_result?;
-                    if config.show_ends {
-                        line.push('$');
-                    }
-                    if config.show_tabs {
-                        line = line.replace('\t', "^I");
-                    }
-                    if config.number_lines {
-                        line_num += 1;
-                        println!("{line_num:>6}\t{line}");
-                    } else if config.number_nonblank_lines {
-                        if line.is_empty() {
-                            println!();
-                        } else {
-                            line_num += 1;
-                            println!("{line_num:>6}\t{line}");
-                        }
-                    } else {
-                        println!("{line}");
-                    }
+/// Will return `Err` if writing output fails.
+pub fn run(config: &Config) -> Result<i32> {
+    let mut out = io::stdout().lock();
+    let mut state = LineState::default();
+    let mut reporter = Reporter::new("cat");
+    for filename in &config.files {
+        let result = open(filename)
+            .map_err(CopyError::Read)
+            .and_then(|mut file| {
+                if config.is_formatted() {
+                    cat_lines(&mut file, &mut out, config, &mut state)
+                } else {
+                    copy(&mut file, &mut out)
                 }
+            });
+        match result {
+            Ok(()) => {}
+            Err(CopyError::Read(err)) => {
+                out.flush()?;
+                reporter.error(filename, &err);
+            }
+            Err(CopyError::Write(err)) => return Err(err.into()),
+        }
+    }
+    out.flush()?;
+    Ok(reporter.status())
+}
+
+/// Copies `file` to `out` unchanged.
+fn copy(file: &mut impl BufRead, out: &mut impl Write) -> Result<(), CopyError> {
+    loop {
+        let buf = match file.fill_buf() {
+            Ok([]) => return Ok(()),
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(CopyError::Read(e)),
+        };
+        out.write_all(buf).map_err(CopyError::Write)?;
+        let len = buf.len();
+        file.consume(len);
+    }
+}
+
+/// Copies `file` to `out` one line at a time, applying numbering and the
+/// display options. Line contents are passed through as raw bytes.
+fn cat_lines(
+    file: &mut impl BufRead,
+    out: &mut impl Write,
+    config: &Config,
+    state: &mut LineState,
+) -> Result<(), CopyError> {
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if file.read_until(b'\n', &mut buf).map_err(CopyError::Read)? == 0 {
+            return Ok(());
+        }
+        print_line(out, &buf, config, state).map_err(CopyError::Write)?;
+    }
+}
+
+/// Prints one line read by `cat_lines`, including its newline if it has one.
+fn print_line(
+    out: &mut impl Write,
+    buf: &[u8],
+    config: &Config,
+    state: &mut LineState,
+) -> io::Result<()> {
+    let (mut line, has_newline) = buf
+        .strip_suffix(b"\n")
+        .map_or((buf, false), |line| (line, true));
+    let blank = !state.mid_line && has_newline && line.is_empty();
+    if blank && state.prev_blank && config.squeeze_blank {
+        return Ok(());
+    }
+    state.prev_blank = blank;
+    if !state.mid_line
+        && (config.number_lines || (config.number_nonblank_lines && !line.is_empty()))
+    {
+        state.line_num += 1;
+        write!(out, "{:>6}\t", state.line_num)?;
+    }
+    // Like GNU, -E marks the carriage return of a CRLF ending as ^M.
+    let mut carriage_return = false;
+    if has_newline && config.show_ends && !config.show_nonprinting {
+        if let Some(stripped) = line.strip_suffix(b"\r") {
+            (line, carriage_return) = (stripped, true);
+        }
+    }
+    write_line(out, line, config)?;
+    if carriage_return {
+        out.write_all(b"^M")?;
+    }
+    if has_newline {
+        if config.show_ends {
+            out.write_all(b"$")?;
+        }
+        out.write_all(b"\n")?;
+    }
+    state.mid_line = !has_newline;
+    Ok(())
+}
+
+/// Writes the contents of a line, without its terminator, using `^I` for
+/// tabs and `^` and `M-` notation for other nonprinting bytes when enabled.
+fn write_line(out: &mut impl Write, line: &[u8], config: &Config) -> io::Result<()> {
+    if !config.show_nonprinting && !config.show_tabs {
+        return out.write_all(line);
+    }
+    for &byte in line {
+        if byte == b'\t' {
+            if config.show_tabs {
+                out.write_all(b"^I")?;
+            } else {
+                out.write_all(b"\t")?;
             }
+        } else if config.show_nonprinting {
+            write_nonprinting(out, byte)?;
+        } else {
+            out.write_all(&[byte])?;
         }
     }
     Ok(())
 }
 
-fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
+/// Writes a byte in the notation used by GNU `cat -v`: control characters
+/// as `^X`, DEL as `^?`, and bytes above 127 prefixed with `M-`.
+fn write_nonprinting(out: &mut impl Write, byte: u8) -> io::Result<()> {
+    let low = if byte >= 128 {
+        out.write_all(b"M-")?;
+        byte - 128
+    } else {
+        byte
+    };
+    match low {
+        0..=31 => out.write_all(&[b'^', low + 64]),
+        127 => out.write_all(b"^?"),
+        _ => out.write_all(&[low]),
     }
 }
 
@@ -138,6 +271,13 @@ pub fn get_args() -> Result<Config> {
                 .long("number")
                 .help("Numbers all output lines"),
         )
+        .arg(
+            Arg::new("squeeze_blank")
+                .action(ArgAction::SetTrue)
+                .short('s')
+                .long("squeeze-blank")
+                .help("Suppress repeated empty output lines"),
+        )
         .arg(
             Arg::new("show_nonprinting_tabs")
                 .action(ArgAction::SetTrue)
@@ -172,12 +312,20 @@ pub fn get_args() -> Result<Config> {
         .cloned()
         .collect();
 
+    let show_all = matches.get_flag("show_all");
+    let show_nonprinting_ends = matches.get_flag("show_nonprinting_ends");
+    let show_nonprinting_tabs = matches.get_flag("show_nonprinting_tabs");
+
     Ok(Config {
         files,
         number_lines: matches.get_flag("number"),
         number_nonblank_lines: matches.get_flag("number_nonblank"),
-        show_ends: matches.get_flag("show_ends"),
-        show_nonprinting: matches.get_flag("show_nonprinting"),
-        show_tabs: matches.get_flag("show_tabs"),
+        show_ends: matches.get_flag("show_ends") || show_all || show_nonprinting_ends,
+        show_nonprinting: matches.get_flag("show_nonprinting")
+            || show_all
+            || show_nonprinting_ends
+            || show_nonprinting_tabs,
+        show_tabs: matches.get_flag("show_tabs") || show_all || show_nonprinting_tabs,
+        squeeze_blank: matches.get_flag("squeeze_blank"),
     })
 }
@@ -1,6 +1,9 @@
 fn main() {
-    if let Err(e) = cat::get_args().and_then(cat::run) {
-        eprintln!("{e}");
-        std::process::exit(1);
+    match cat::get_args().and_then(|config| cat::run(&config)) {
+        Ok(status) => std::process::exit(status),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
     }
 }
@@ -11,6 +11,11 @@ const TABS: &str = "tests/inputs/tabs.txt";
 const FOX: &str = "tests/inputs/fox.txt";
 const SPIDERS: &str = "tests/inputs/spiders.txt";
 const BUSTLE: &str = "tests/inputs/the-bustle.txt";
+const BYTES: &str = "tests/inputs/bytes.bin";
+const LATIN1: &str = "tests/inputs/latin1.txt";
+const CRLF: &str = "tests/inputs/crlf.txt";
+const NO_NEWLINE: &str = "tests/inputs/no-newline.txt";
+const BLANKS: &str = "tests/inputs/blanks.txt";
 
 #[test]
 fn usage() -> Result<()> {
@@ -40,12 +45,58 @@ fn gen_bad_file() -> String {
 #[test]
 fn skips_bad_file() -> Result<()> {
     let bad = gen_bad_file();
-    let expected = format!("{bad}: .* [(]os error 2[)]");
+    let expected = format!("cat: {bad}: No such file or directory\n");
     Command::cargo_bin(PRG)?
         .arg(&bad)
         .assert()
-        .success()
-        .stderr(predicate::str::is_match(expected)?);
+        .code(1)
+        .stderr(expected);
+    Ok(())
+}
+
+#[test]
+fn continues_after_bad_file() -> Result<()> {
+    let bad = gen_bad_file();
+    let expected = fs::read_to_string("tests/expected/fox.txt.out")?;
+    Command::cargo_bin(PRG)?
+        .args([&bad, FOX])
+        .assert()
+        .code(1)
+        .stdout(expected)
+        .stderr(format!("cat: {bad}: No such file or directory\n"));
+    Ok(())
+}
+
+#[test]
+fn skips_directory() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/fox.txt.n.out")?;
+    Command::cargo_bin(PRG)?
+        .args(["-n", "tests/inputs", FOX])
+        .assert()
+        .code(1)
+        .stdout(expected)
+        .stderr("cat: tests/inputs: Is a directory\n");
+    Ok(())
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn continues_after_read_error() -> Result<()> {
+    // Opening /proc/self/mem succeeds, but reading its first page fails.
+    for (args, expected_file) in [
+        (&["/proc/self/mem", FOX][..], "tests/expected/fox.txt.out"),
+        (
+            &["-n", "/proc/self/mem", FOX],
+            "tests/expected/fox.txt.n.out",
+        ),
+    ] {
+        Command::cargo_bin(PRG)?
+            .args(args)
+            .assert()
+            .code(1)
+            .stdout(fs::read_to_string(expected_file)?)
+            .stderr("cat: /proc/self/mem: Input/output error\n");
+    }
     Ok(())
 }
 
@@ -59,6 +110,28 @@ fn run(args: &[&str], expected_file: &str) -> Result<()> {
     Ok(())
 }
 
+fn run_bytes(args: &[&str], expected_file: &str) -> Result<()> {
+    let expected = fs::read(expected_file)?;
+    Command::cargo_bin(PRG)?
+        .args(args)
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
+fn run_stdin_bytes(input_file: &str, args: &[&str], expected_file: &str) -> Result<()> {
+    let input = fs::read(input_file)?;
+    let expected = fs::read(expected_file)?;
+    Command::cargo_bin(PRG)?
+        .args(args)
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
 fn run_stdin(input_file: &str, args: &[&str], expected_file: &str) -> Result<()> {
     let input = fs::read_to_string(input_file)?;
     let expected = fs::read_to_string(expected_file)?;
@@ -103,6 +176,15 @@ fn bustle_stdin_ends() -> Result<()> {
     )
 }
 
+#[test]
+fn bustle_stdin_show_all() -> Result<()> {
+    run_stdin(
+        BUSTLE,
+        &["-A", "-"],
+        "tests/expected/the-bustle.txt.A.stdin.out",
+    )
+}
+
 #[test]
 fn empty() -> Result<()> {
     run(&[EMPTY], "tests/expected/empty.txt.out")
@@ -128,10 +210,68 @@ fn tabs() -> Result<()> {
     run(&["-T", TABS], "tests/expected/tabs.txt.T.out")
 }
 
-// #[test]
-// fn nonprinting() -> Result<()> {
-//     run(&["-v", BUSTLE], "tests/expected/the-bustle.txt.v.out")
-// }
+#[test]
+fn nonprinting() -> Result<()> {
+    run(&["-v", BUSTLE], "tests/expected/the-bustle.txt.v.out")
+}
+
+#[test]
+fn bytes_nonprinting() -> Result<()> {
+    run(
+        &["--show-nonprinting", BYTES],
+        "tests/expected/bytes.bin.v.out",
+    )
+}
+
+#[test]
+fn bytes_nonprinting_tabs() -> Result<()> {
+    run(&["-v", "-T", BYTES], "tests/expected/bytes.bin.vT.out")
+}
+
+#[test]
+fn tabs_show_all() -> Result<()> {
+    run(&["--show-all", TABS], "tests/expected/tabs.txt.A.out")
+}
+
+#[test]
+fn tabs_e() -> Result<()> {
+    run(&["-e", TABS], "tests/expected/tabs.txt.e.out")
+}
+
+#[test]
+fn tabs_t() -> Result<()> {
+    run(&["-t", TABS], "tests/expected/tabs.txt.t.out")
+}
+
+#[test]
+fn bustle_show_all() -> Result<()> {
+    run(&["-A", BUSTLE], "tests/expected/the-bustle.txt.A.out")
+}
+
+#[test]
+fn bustle_e() -> Result<()> {
+    run(&["-e", BUSTLE], "tests/expected/the-bustle.txt.e.out")
+}
+
+#[test]
+fn bustle_t() -> Result<()> {
+    run(&["-t", BUSTLE], "tests/expected/the-bustle.txt.t.out")
+}
+
+#[test]
+fn bytes_show_all() -> Result<()> {
+    run(&["-A", BYTES], "tests/expected/bytes.bin.A.out")
+}
+
+#[test]
+fn bytes_e() -> Result<()> {
+    run(&["-e", BYTES], "tests/expected/bytes.bin.e.out")
+}
+
+#[test]
+fn bytes_t() -> Result<()> {
+    run(&["-t", BYTES], "tests/expected/bytes.bin.t.out")
+}
 
 #[test]
 fn fox() -> Result<()> {
@@ -199,6 +339,161 @@ fn bustle_ends() -> Result<()> {
     run(&["-E", BUSTLE], "tests/expected/the-bustle.txt.E.out")
 }
 
+#[test]
+fn bytes() -> Result<()> {
+    run_bytes(&[BYTES], "tests/expected/bytes.bin.out")
+}
+
+#[test]
+fn bytes_stdin() -> Result<()> {
+    run_stdin_bytes(BYTES, &[], "tests/expected/bytes.bin.out")
+}
+
+#[test]
+fn latin1() -> Result<()> {
+    run_bytes(&[LATIN1], "tests/expected/latin1.txt.out")
+}
+
+#[test]
+fn latin1_n() -> Result<()> {
+    run_bytes(&["-n", LATIN1], "tests/expected/latin1.txt.n.out")
+}
+
+#[test]
+fn latin1_b() -> Result<()> {
+    run_bytes(&["-b", LATIN1], "tests/expected/latin1.txt.b.out")
+}
+
+#[test]
+fn latin1_ends() -> Result<()> {
+    run_bytes(&["-E", LATIN1], "tests/expected/latin1.txt.E.out")
+}
+
+#[test]
+fn latin1_show_all() -> Result<()> {
+    run_bytes(&["-A", LATIN1], "tests/expected/latin1.txt.A.out")
+}
+
+#[test]
+fn crlf() -> Result<()> {
+    run_bytes(&[CRLF], "tests/expected/crlf.txt.out")
+}
+
+#[test]
+fn crlf_n() -> Result<()> {
+    run_bytes(&["-n", CRLF], "tests/expected/crlf.txt.n.out")
+}
+
+#[test]
+fn crlf_b() -> Result<()> {
+    run_bytes(&["-b", CRLF], "tests/expected/crlf.txt.b.out")
+}
+
+#[test]
+fn crlf_ends() -> Result<()> {
+    run_bytes(&["-E", CRLF], "tests/expected/crlf.txt.E.out")
+}
+
+#[test]
+fn crlf_show_all() -> Result<()> {
+    run_bytes(&["-A", CRLF], "tests/expected/crlf.txt.A.out")
+}
+
+#[test]
+fn no_newline() -> Result<()> {
+    run_bytes(&[NO_NEWLINE], "tests/expected/no-newline.txt.out")
+}
+
+#[test]
+fn no_newline_n() -> Result<()> {
+    run_bytes(&["-n", NO_NEWLINE], "tests/expected/no-newline.txt.n.out")
+}
+
+#[test]
+fn no_newline_b() -> Result<()> {
+    run_bytes(&["-b", NO_NEWLINE], "tests/expected/no-newline.txt.b.out")
+}
+
+#[test]
+fn no_newline_ends() -> Result<()> {
+    run_bytes(&["-E", NO_NEWLINE], "tests/expected/no-newline.txt.E.out")
+}
+
+#[test]
+fn no_newline_show_all() -> Result<()> {
+    run_bytes(&["-A", NO_NEWLINE], "tests/expected/no-newline.txt.A.out")
+}
+
+#[test]
+fn latin1_stdin_n() -> Result<()> {
+    run_stdin_bytes(LATIN1, &["-n"], "tests/expected/latin1.txt.n.out")
+}
+
+#[test]
+fn joined() -> Result<()> {
+    run_bytes(&[NO_NEWLINE, LATIN1, CRLF], "tests/expected/joined.out")
+}
+
+#[test]
+fn joined_n() -> Result<()> {
+    run_bytes(
+        &["-n", NO_NEWLINE, LATIN1, CRLF],
+        "tests/expected/joined.n.out",
+    )
+}
+
+#[test]
+fn joined_ends() -> Result<()> {
+    run_bytes(
+        &["-E", NO_NEWLINE, LATIN1, CRLF],
+        "tests/expected/joined.E.out",
+    )
+}
+
+#[test]
+fn blanks_squeeze() -> Result<()> {
+    run(&["-s", BLANKS], "tests/expected/blanks.txt.s.out")
+}
+
+#[test]
+fn blanks_squeeze_n() -> Result<()> {
+    run(&["-s", "-n", BLANKS], "tests/expected/blanks.txt.sn.out")
+}
+
+#[test]
+fn blanks_squeeze_b() -> Result<()> {
+    run(&["-sb", BLANKS], "tests/expected/blanks.txt.sb.out")
+}
+
+#[test]
+fn blanks_squeeze_ends() -> Result<()> {
+    run(
+        &["--squeeze-blank", "--show-ends", BLANKS],
+        "tests/expected/blanks.txt.sE.out",
+    )
+}
+
+#[test]
+fn blanks_stdin_squeeze() -> Result<()> {
+    run_stdin(BLANKS, &["-s"], "tests/expected/blanks.txt.s.stdin.out")
+}
+
+#[test]
+fn squeezed_across_files() -> Result<()> {
+    run(
+        &["-s", BLANKS, BLANKS, NO_NEWLINE, BLANKS],
+        "tests/expected/squeezed.s.out",
+    )
+}
+
+#[test]
+fn squeezed_across_files_n() -> Result<()> {
+    run(
+        &["-sn", BLANKS, BLANKS, NO_NEWLINE, BLANKS],
+        "tests/expected/squeezed.sn.out",
+    )
+}
+
 #[test]
 fn all() -> Result<()> {
     run(&[FOX, SPIDERS, BUSTLE], "tests/expected/all.out")
@@ -218,3 +513,13 @@ fn all_b() -> Result<()> {
 fn all_ends() -> Result<()> {
     run(&[FOX, SPIDERS, BUSTLE, "-E"], "tests/expected/all.E.out")
 }
+
+#[test]
+fn all_show_all() -> Result<()> {
+    run(&[FOX, SPIDERS, BUSTLE, "-A"], "tests/expected/all.A.out")
+}
+
+#[test]
+fn all_squeeze() -> Result<()> {
+    run(&[FOX, SPIDERS, BUSTLE, "-s"], "tests/expected/all.s.out")
+}
@@ -0,0 +1,49 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Opens `filename` for buffered reading, or standard input if it is `-`.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be opened, or with
+/// [`io::ErrorKind::IsADirectory`] if it is a directory.
+pub fn open(filename: impl AsRef<Path>) -> io::Result<Box<dyn BufRead>> {
+    open_with_capacity(filename, 8 * 1024)
+}
+
+/// Like [`open`], reading through a buffer of `capacity` bytes.
+///
+/// # Errors
+///
+/// Will return `Err` if the file cannot be opened, or with
+/// [`io::ErrorKind::IsADirectory`] if it is a directory.
+pub fn open_with_capacity(
+    filename: impl AsRef<Path>,
+    capacity: usize,
+) -> io::Result<Box<dyn BufRead>> {
+    let filename = filename.as_ref();
+    if filename.as_os_str() == "-" {
+        return Ok(Box::new(BufReader::with_capacity(capacity, io::stdin())));
+    }
+    let file = File::open(filename)?;
+    if file.metadata()?.is_dir() {
+        return Err(io::ErrorKind::IsADirectory.into());
+    }
+    Ok(Box::new(BufReader::with_capacity(capacity, file)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open() {
+        assert!(open("Cargo.toml").is_ok());
+        assert!(open("-").is_ok());
+        let dir = open("src").map(|_| ()).map_err(|e| e.kind());
+        assert_eq!(dir, Err(io::ErrorKind::IsADirectory));
+        let missing = open("no-such-file").map(|_| ()).map_err(|e| e.kind());
+        assert_eq!(missing, Err(io::ErrorKind::NotFound));
+    }
+}
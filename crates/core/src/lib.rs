@@ -0,0 +1,11 @@
+//! Input handling, error reporting and size parsing shared by the utilities,
+//! so that they open their operands, describe failures and read sizes the
+//! same way GNU coreutils do.
+
+mod input;
+mod report;
+mod size;
+
+pub use input::{open, open_with_capacity};
+pub use report::{error_message, format_error, Reporter};
+pub use size::{parse_size, ParseSizeError};
@@ -0,0 +1,89 @@
+use std::fmt::Display;
+use std::io;
+
+/// Describes an I/O error the way GNU utilities do, without the
+/// `(os error N)` suffix Rust appends.
+#[must_use]
+pub fn error_message(err: &io::Error) -> String {
+    match err.kind() {
+        io::ErrorKind::NotFound => "No such file or directory".to_owned(),
+        io::ErrorKind::PermissionDenied => "Permission denied".to_owned(),
+        io::ErrorKind::IsADirectory => "Is a directory".to_owned(),
+        _ => {
+            let message = err.to_string();
+            match message.find(" (os error") {
+                Some(end) => message[..end].to_owned(),
+                None => message,
+            }
+        }
+    }
+}
+
+/// Formats `err` as `util: operand: message`.
+#[must_use]
+pub fn format_error(util: &str, operand: impl Display, err: &io::Error) -> String {
+    format!("{util}: {operand}: {}", error_message(err))
+}
+
+/// Reports errors on standard error while a utility carries on with its other
+/// operands, and accumulates its exit status: `1` once anything has failed.
+#[derive(Debug)]
+pub struct Reporter {
+    util: &'static str,
+    failed: bool,
+}
+
+impl Reporter {
+    #[must_use]
+    pub const fn new(util: &'static str) -> Self {
+        Self {
+            util,
+            failed: false,
+        }
+    }
+
+    /// Prints `util: operand: message` for `err` and records the failure.
+    pub fn error(&mut self, operand: impl Display, err: &io::Error) {
+        eprintln!("{}", format_error(self.util, operand, err));
+        self.failed = true;
+    }
+
+    /// Prints `util: message` and records the failure.
+    pub fn message(&mut self, message: impl Display) {
+        eprintln!("{}: {message}", self.util);
+        self.failed = true;
+    }
+
+    /// Returns the exit status for the errors reported so far.
+    #[must_use]
+    pub const fn status(&self) -> i32 {
+        if self.failed {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_message() {
+        let err = io::Error::from(io::ErrorKind::NotFound);
+        assert_eq!(error_message(&err), "No such file or directory");
+        let err = io::Error::from_raw_os_error(5);
+        assert_eq!(error_message(&err), "Input/output error");
+        let err = io::Error::from(io::ErrorKind::IsADirectory);
+        assert_eq!(format_error("cat", "dir", &err), "cat: dir: Is a directory");
+    }
+
+    #[test]
+    fn test_status() {
+        let mut reporter = Reporter::new("test");
+        assert_eq!(reporter.status(), 0);
+        reporter.message("something failed");
+        assert_eq!(reporter.status(), 1);
+    }
+}
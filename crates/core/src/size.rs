@@ -0,0 +1,137 @@
+//! Sizes with the multiplier suffixes accepted by GNU coreutils, such as
+//! `512`, `2K`, `10MB` or `1GiB`.
+use std::fmt;
+
+/// The reasons a size can fail to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseSizeError {
+    /// The string is empty.
+    Empty,
+    /// The string does not start with a decimal digit.
+    InvalidDigit,
+    /// The digits are followed by an unknown multiplier suffix.
+    InvalidSuffix(String),
+    /// The size does not fit in a `u64`.
+    TooLarge,
+}
+
+impl fmt::Display for ParseSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "cannot parse size from empty string"),
+            Self::InvalidDigit => write!(f, "invalid digit found in string"),
+            Self::InvalidSuffix(suffix) => write!(f, "invalid suffix '{suffix}'"),
+            Self::TooLarge => write!(f, "Value too large for defined data type"),
+        }
+    }
+}
+
+impl std::error::Error for ParseSizeError {}
+
+/// Parses a decimal size with an optional multiplier suffix.
+///
+/// The suffixes are `b` 512, `kB` 1000, `K` 1024, `MB` 1000*1000, `M` 1024*1024, and so on
+/// for `G`, `T`, `P`, `E`, `Z`, `Y`, `R` and `Q`. Binary prefixes can be
+/// used too: `KiB`=`K`, `MiB`=`M`, and so on.
+///
+/// # Errors
+///
+/// Will return `Err` if `size` is not a number followed by a known suffix,
+/// or if the result overflows a `u64`.
+pub fn parse_size(size: &str) -> Result<u64, ParseSizeError> {
+    if size.is_empty() {
+        return Err(ParseSizeError::Empty);
+    }
+    let digits_end = size
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(size.len());
+    let (digits, suffix) = size.split_at(digits_end);
+    if digits.is_empty() {
+        return Err(ParseSizeError::InvalidDigit);
+    }
+    let multiplier =
+        multiplier(suffix).ok_or_else(|| ParseSizeError::InvalidSuffix(suffix.to_owned()))?;
+    let num: u128 = digits.parse().map_err(|_| ParseSizeError::TooLarge)?;
+    num.checked_mul(multiplier)
+        .and_then(|size| u64::try_from(size).ok())
+        .ok_or(ParseSizeError::TooLarge)
+}
+
+/// Returns the multiplier for `suffix`, which is large enough for `Q` to
+/// need a `u128`.
+fn multiplier(suffix: &str) -> Option<u128> {
+    match suffix {
+        "" => return Some(1),
+        "b" => return Some(512),
+        _ => {}
+    }
+    let mut chars = suffix.chars();
+    let exponent = match chars.next()? {
+        'k' | 'K' => 1,
+        'm' | 'M' => 2,
+        'G' => 3,
+        'T' => 4,
+        'P' => 5,
+        'E' => 6,
+        'Z' => 7,
+        'Y' => 8,
+        'R' => 9,
+        'Q' => 10,
+        _ => return None,
+    };
+    let base: u128 = match chars.as_str() {
+        "" | "iB" => 1024,
+        "B" => 1000,
+        _ => return None,
+    };
+    Some(base.pow(exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("0"), Ok(0));
+        assert_eq!(parse_size("42"), Ok(42));
+        assert_eq!(parse_size("2b"), Ok(1024));
+        assert_eq!(parse_size("1kB"), Ok(1000));
+        assert_eq!(parse_size("1k"), Ok(1024));
+        assert_eq!(parse_size("1K"), Ok(1024));
+        assert_eq!(parse_size("1KiB"), Ok(1024));
+        assert_eq!(parse_size("3MB"), Ok(3_000_000));
+        assert_eq!(parse_size("3M"), Ok(3 * 1024 * 1024));
+        assert_eq!(parse_size("1GB"), Ok(1_000_000_000));
+        assert_eq!(parse_size("1GiB"), Ok(1 << 30));
+        assert_eq!(parse_size("1T"), Ok(1 << 40));
+        assert_eq!(parse_size("1P"), Ok(1 << 50));
+        assert_eq!(parse_size("1EB"), Ok(1_000_000_000_000_000_000));
+        assert_eq!(parse_size("15E"), Ok(15 << 60));
+        assert_eq!(parse_size("18446744073709551615"), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_parse_size_errors() {
+        assert_eq!(parse_size(""), Err(ParseSizeError::Empty));
+        assert_eq!(parse_size("K"), Err(ParseSizeError::InvalidDigit));
+        assert_eq!(parse_size("-1"), Err(ParseSizeError::InvalidDigit));
+        for suffix in ["g", "B", "bB", "KB2", " ", "x"] {
+            assert_eq!(
+                parse_size(&format!("1{suffix}")),
+                Err(ParseSizeError::InvalidSuffix(suffix.to_owned()))
+            );
+        }
+        assert_eq!(parse_size("16E"), Err(ParseSizeError::TooLarge));
+        assert_eq!(parse_size("1Z"), Err(ParseSizeError::TooLarge));
+        assert_eq!(parse_size("1Q"), Err(ParseSizeError::TooLarge));
+        assert_eq!(
+            parse_size("18446744073709551616"),
+            Err(ParseSizeError::TooLarge)
+        );
+        assert_eq!(
+            parse_size("999999999999999999999999999999999999999999"),
+            Err(ParseSizeError::TooLarge)
+        );
+    }
+}
@@ -1,8 +1,31 @@
 #!/usr/bin/env bash
 
+# Use GNU echo rather than the shell builtin, whose escapes differ.
+ECHO="env echo"
+
 OUTDIR="tests/expected"
 [[ ! -d "$OUTDIR" ]] && mkdir -p "$OUTDIR"
-echo "Hello there" > $OUTDIR/hello1.txt
-echo "Hello"  "there" > $OUTDIR/hello2.txt
-echo -n "Hello  there" > $OUTDIR/hello1.n.txt
-echo -n "Hello" "there" > $OUTDIR/hello2.n.txt
+$ECHO "Hello there" > $OUTDIR/hello1.txt
+$ECHO "Hello"  "there" > $OUTDIR/hello2.txt
+$ECHO -n "Hello  there" > $OUTDIR/hello1.n.txt
+$ECHO -n "Hello" "there" > $OUTDIR/hello2.n.txt
+
+$ECHO -e 'tab\there\nnew line\\backslash' > $OUTDIR/escapes.e.txt
+$ECHO -E 'tab\there\nnew line\\backslash' > $OUTDIR/escapes.E.txt
+$ECHO -e -E 'tab\there' > $OUTDIR/escapes.eE.txt
+$ECHO -E -e 'tab\there' > $OUTDIR/escapes.Ee.txt
+$ECHO -e '\a\b\e\f\r\v' > $OUTDIR/controls.e.txt
+$ECHO -e 'stop\c here' 'and here' > $OUTDIR/stop.e.txt
+$ECHO -e '\0101\0 \101\60\7777 \08' > $OUTDIR/octal.e.txt
+$ECHO -e '\x41\x4a\xAf\x4g \xz \xff\xfe' > $OUTDIR/hex.e.txt
+$ECHO -e 'unknown \q \z' 'trailing\' > $OUTDIR/unknown.e.txt
+$ECHO -n -e 'no\tnewline' > $OUTDIR/escapes.ne.txt
+
+$ECHO > $OUTDIR/no-args.txt
+$ECHO "Hello there" -n > $OUTDIR/trailing-n.txt
+$ECHO -x foo > $OUTDIR/unknown-option.txt
+$ECHO -nx foo > $OUTDIR/unknown-cluster.txt
+$ECHO -- foo > $OUTDIR/double-dash.txt
+$ECHO - foo > $OUTDIR/dash.txt
+$ECHO -nnEe 'a\tb' -n > $OUTDIR/cluster.txt
+$ECHO --help foo > $OUTDIR/help-with-operand.txt
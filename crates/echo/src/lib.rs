@@ -0,0 +1,287 @@
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+
+use clap::{Arg, ArgAction, Command};
+
+/// The parsed command line: GNU echo takes options only from a leading run of
+/// arguments made up of `-n`, `-e` and `-E`, and prints everything else.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// The arguments to print, separated by spaces.
+    pub text: Vec<OsString>,
+    /// Do not print the trailing newline (`-n`).
+    pub omit_newline: bool,
+    /// Interpret backslash escapes (`-e`).
+    pub escapes: bool,
+}
+
+/// Parses the process arguments, printing help or version information and
+/// exiting if `--help` or `--version` is the only argument.
+#[must_use]
+pub fn get_args() -> Config {
+    let args: Vec<OsString> = env::args_os().skip(1).collect();
+    // Like GNU echo, --help and --version are only recognised on their own.
+    if let [arg] = args.as_slice() {
+        if arg == "--help" || arg == "--version" {
+            command().get_matches();
+        }
+    }
+    parse_args(args)
+}
+
+/// Writes the text of `config` to `out`.
+///
+/// # Errors
+///
+/// Will return `Err` if writing to `out` fails.
+pub fn run<W: Write>(config: &Config, mut out: W) -> io::Result<()> {
+    let mut output = Vec::new();
+    let mut stopped = false;
+    for (i, arg) in config.text.iter().enumerate() {
+        if i > 0 {
+            output.push(b' ');
+        }
+        if config.escapes {
+            if unescape(arg.as_encoded_bytes(), &mut output).is_break() {
+                stopped = true;
+                break;
+            }
+        } else {
+            output.extend_from_slice(arg.as_encoded_bytes());
+        }
+    }
+    if !config.omit_newline && !stopped {
+        output.push(b'\n');
+    }
+    out.write_all(&output)?;
+    out.flush()
+}
+
+/// Describes the options for `--help`; the arguments themselves are parsed by
+/// `parse_args`.
+fn command() -> Command {
+    Command::new("echo")
+        .version("0.1.0")
+        .author("Matt Cook <matt@mattcook.dev>")
+        .about("Rust echo")
+        .override_usage("echo [SHORT-OPTION]... [STRING]...\n       echo LONG-OPTION")
+        .arg(
+            Arg::new("text")
+                .value_name("STRING")
+                .help("Input text")
+                .num_args(0..),
+        )
+        .arg(
+            Arg::new("omit_newline")
+                .short('n')
+                .action(ArgAction::SetTrue)
+                .help("Do not print newline"),
+        )
+        .arg(
+            Arg::new("escapes")
+                .short('e')
+                .action(ArgAction::SetTrue)
+                .help("Enable interpretation of backslash escapes"),
+        )
+        .arg(
+            Arg::new("no_escapes")
+                .short('E')
+                .action(ArgAction::SetTrue)
+                .help("Disable interpretation of backslash escapes (default)"),
+        )
+}
+
+/// Parses `args`, which exclude the program name.
+///
+/// Options are taken from the leading arguments that consist of `-` followed
+/// only by `n`, `e` and `E`, the last of `-e` and `-E` winning. The first
+/// other argument, including `-` and `--`, starts the text.
+#[must_use]
+pub fn parse_args<I: IntoIterator<Item = OsString>>(args: I) -> Config {
+    let args: Vec<OsString> = args.into_iter().collect();
+    let mut omit_newline = false;
+    let mut escapes = false;
+    let mut options = 0;
+    for arg in &args {
+        let flags = match arg.as_encoded_bytes() {
+            [b'-', flags @ ..] if !flags.is_empty() => flags,
+            _ => break,
+        };
+        if !flags.iter().all(|f| matches!(f, b'n' | b'e' | b'E')) {
+            break;
+        }
+        for flag in flags {
+            match flag {
+                b'n' => omit_newline = true,
+                b'e' => escapes = true,
+                _ => escapes = false,
+            }
+        }
+        options += 1;
+    }
+    Config {
+        text: args.into_iter().skip(options).collect(),
+        omit_newline,
+        escapes,
+    }
+}
+
+/// Appends `arg` to `output` with its backslash escapes interpreted as GNU
+/// echo does, breaking at `\c`, after which nothing more is printed.
+/// Unrecognised escapes are printed as they are.
+pub fn unescape(arg: &[u8], output: &mut Vec<u8>) -> ControlFlow<()> {
+    let mut bytes = arg.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            output.push(b);
+            continue;
+        }
+        let Some(escape) = bytes.next() else {
+            output.push(b'\\');
+            break;
+        };
+        let byte = match escape {
+            b'\\' => b'\\',
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'c' => return ControlFlow::Break(()),
+            b'e' => 0x1b,
+            b'f' => 0x0c,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'v' => 0x0b,
+            b'x' => {
+                let mut value = None;
+                for _ in 0..2 {
+                    match bytes.peek().and_then(|&d| char::from(d).to_digit(16)) {
+                        Some(digit) => {
+                            value = Some(value.unwrap_or(0) * 16 + digit);
+                            bytes.next();
+                        }
+                        None => break,
+                    }
+                }
+                let Some(value) = value else {
+                    output.extend_from_slice(b"\\x");
+                    continue;
+                };
+                // At most two hex digits, so the value fits in a byte.
+                value.to_le_bytes()[0]
+            }
+            // `\0` may be followed by up to three octal digits and `\1` to
+            // `\7` by up to two more; larger values wrap around to a byte.
+            b'0'..=b'7' => {
+                let mut value = u32::from(escape - b'0');
+                let max_digits = if escape == b'0' { 3 } else { 2 };
+                for _ in 0..max_digits {
+                    match bytes.peek() {
+                        Some(&d @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(d - b'0');
+                            bytes.next();
+                        }
+                        _ => break,
+                    }
+                }
+                value.to_le_bytes()[0]
+            }
+            _ => {
+                output.extend_from_slice(&[b'\\', escape]);
+                continue;
+            }
+        };
+        output.push(byte);
+    }
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    fn echo(config: &Config) -> Vec<u8> {
+        let mut out = Vec::new();
+        assert!(run(config, &mut out).is_ok());
+        out
+    }
+
+    fn unescaped(arg: &[u8]) -> (Vec<u8>, ControlFlow<()>) {
+        let mut output = Vec::new();
+        let flow = unescape(arg, &mut output);
+        (output, flow)
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(args(&[])), Config::default());
+        assert_eq!(
+            parse_args(args(&["-n", "-e", "a", "-E"])),
+            Config {
+                text: args(&["a", "-E"]),
+                omit_newline: true,
+                escapes: true,
+            }
+        );
+        assert_eq!(
+            parse_args(args(&["-eEn", "-nE", "a"])),
+            Config {
+                text: args(&["a"]),
+                omit_newline: true,
+                escapes: false,
+            }
+        );
+        for text in [
+            &["-x", "-n"][..],
+            &["-nx"],
+            &["--", "-n"],
+            &["-"],
+            &["--help"],
+        ] {
+            assert_eq!(
+                parse_args(args(text)),
+                Config {
+                    text: args(text),
+                    ..Config::default()
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_unescape() {
+        let cont = ControlFlow::Continue(());
+        assert_eq!(unescaped(br"a\\b\tc"), (b"a\\b\tc".to_vec(), cont));
+        assert_eq!(
+            unescaped(br"\a\b\e\f\n\r\v"),
+            (b"\x07\x08\x1b\x0c\n\r\x0b".to_vec(), cont)
+        );
+        assert_eq!(unescaped(br"\0101\101\0\08"), (b"AA\0\08".to_vec(), cont));
+        assert_eq!(unescaped(br"\7777\0777"), (b"\xff7\xff".to_vec(), cont));
+        assert_eq!(
+            unescaped(br"\x41\xfF\x4g\xz"),
+            (b"A\xff\x04g\\xz".to_vec(), cont)
+        );
+        assert_eq!(unescaped(br"\q\"), (b"\\q\\".to_vec(), cont));
+        assert_eq!(
+            unescaped(br"ab\cde"),
+            (b"ab".to_vec(), ControlFlow::Break(()))
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let config = parse_args(args(&["hello", "there"]));
+        assert_eq!(echo(&config), b"hello there\n");
+        let config = parse_args(args(&["-n", "hello"]));
+        assert_eq!(echo(&config), b"hello");
+        let config = parse_args(args(&["-e", "a\\tb", "c\\cd", "e"]));
+        assert_eq!(echo(&config), b"a\tb c");
+        assert_eq!(echo(&Config::default()), b"\n");
+    }
+}
@@ -1,31 +1,8 @@
-use clap::{Arg, ArgAction, Command};
+use std::io;
 
 fn main() {
-    let matches = Command::new("echo")
-        .version("0.1.0")
-        .author("Matt Cook <matt@mattcook.dev>")
-        .about("Rust echo")
-        .arg(
-            Arg::new("text")
-                .value_name("TEXT")
-                .help("Input text")
-                .required(true)
-                .num_args(1..),
-        )
-        .arg(
-            Arg::new("omit_newline")
-                .short('n')
-                .action(ArgAction::SetTrue)
-                .help("Do not print newline"),
-        )
-        .get_matches();
-
-    let text: Vec<String> = matches
-        .get_many("text")
-        .expect("text is required")
-        .cloned()
-        .collect();
-
-    let omit_newline = matches.get_flag("omit_newline");
-    print!("{}{}", text.join(" "), if omit_newline { "" } else { "\n" });
+    if let Err(e) = echo::run(&echo::get_args(), io::stdout().lock()) {
+        eprintln!("echo: {e}");
+        std::process::exit(1);
+    }
 }
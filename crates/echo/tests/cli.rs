@@ -7,11 +7,27 @@ use predicates::prelude::*;
 type TestResult = Result<(), Box<dyn std::error::Error>>;
 
 #[test]
-fn dies_no_args() -> TestResult {
+fn no_args() -> TestResult {
+    run(&[], "tests/expected/no-args.txt")
+}
+
+#[test]
+fn help() -> TestResult {
+    let mut cmd = Command::cargo_bin("echo")?;
+    cmd.arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage"));
+    Ok(())
+}
+
+#[test]
+fn version() -> TestResult {
     let mut cmd = Command::cargo_bin("echo")?;
-    cmd.assert()
-        .failure()
-        .stderr(predicate::str::contains("Usage"));
+    cmd.arg("--version")
+        .assert()
+        .success()
+        .stdout("echo 0.1.0\n");
     Ok(())
 }
 
@@ -33,7 +49,7 @@ fn hello2() -> TestResult {
 
 #[test]
 fn hello1_no_newline() -> TestResult {
-    run(&["Hello  there", "-n"], "tests/expected/hello1.n.txt")
+    run(&["-n", "Hello  there"], "tests/expected/hello1.n.txt")
 }
 
 #[test]
@@ -41,9 +57,113 @@ fn hello2_no_newline() -> TestResult {
     run(&["-n", "Hello", "there"], "tests/expected/hello2.n.txt")
 }
 
+#[test]
+fn trailing_option_is_text() -> TestResult {
+    run(&["Hello there", "-n"], "tests/expected/trailing-n.txt")
+}
+
+#[test]
+fn unknown_option_is_text() -> TestResult {
+    run(&["-x", "foo"], "tests/expected/unknown-option.txt")?;
+    run(&["-nx", "foo"], "tests/expected/unknown-cluster.txt")
+}
+
+#[test]
+fn dashes_are_text() -> TestResult {
+    run(&["--", "foo"], "tests/expected/double-dash.txt")?;
+    run(&["-", "foo"], "tests/expected/dash.txt")
+}
+
+#[test]
+fn option_clusters() -> TestResult {
+    run(&["-nnEe", "a\\tb", "-n"], "tests/expected/cluster.txt")
+}
+
+#[test]
+fn help_with_operand_is_text() -> TestResult {
+    run(&["--help", "foo"], "tests/expected/help-with-operand.txt")
+}
+
+#[test]
+fn escapes() -> TestResult {
+    run(
+        &["-e", "tab\\there\\nnew line\\\\backslash"],
+        "tests/expected/escapes.e.txt",
+    )
+}
+
+#[test]
+fn escapes_disabled() -> TestResult {
+    run(
+        &["-E", "tab\\there\\nnew line\\\\backslash"],
+        "tests/expected/escapes.E.txt",
+    )
+}
+
+#[test]
+fn escapes_last_option_wins() -> TestResult {
+    run(&["-e", "-E", "tab\\there"], "tests/expected/escapes.eE.txt")?;
+    run(&["-E", "-e", "tab\\there"], "tests/expected/escapes.Ee.txt")
+}
+
+#[test]
+fn escapes_no_newline() -> TestResult {
+    run(
+        &["-n", "-e", "no\\tnewline"],
+        "tests/expected/escapes.ne.txt",
+    )
+}
+
+#[test]
+fn control_escapes() -> TestResult {
+    run(
+        &["-e", "\\a\\b\\e\\f\\r\\v"],
+        "tests/expected/controls.e.txt",
+    )
+}
+
+#[test]
+fn stop_escape() -> TestResult {
+    run(
+        &["-e", "stop\\c here", "and here"],
+        "tests/expected/stop.e.txt",
+    )
+}
+
+#[test]
+fn octal_escapes() -> TestResult {
+    run_bytes(
+        &["-e", "\\0101\\0 \\101\\60\\7777 \\08"],
+        "tests/expected/octal.e.txt",
+    )
+}
+
+#[test]
+fn hex_escapes() -> TestResult {
+    run_bytes(
+        &["-e", "\\x41\\x4a\\xAf\\x4g \\xz \\xff\\xfe"],
+        "tests/expected/hex.e.txt",
+    )
+}
+
+#[test]
+fn unknown_escapes() -> TestResult {
+    run(
+        &["-e", "unknown \\q \\z", "trailing\\"],
+        "tests/expected/unknown.e.txt",
+    )
+}
+
 fn run(args: &[&str], expected_file: &str) -> TestResult {
     let expected = fs::read_to_string(expected_file)?;
     let mut cmd = Command::cargo_bin("echo")?;
     cmd.args(args).assert().success().stdout(expected);
     Ok(())
 }
+
+fn run_bytes(args: &[&str], expected_file: &str) -> TestResult {
+    let expected = fs::read(expected_file)?;
+    let mut cmd = Command::cargo_bin("echo")?;
+    cmd.args(args).assert().success().stdout(expected);
+    Ok(())
+}
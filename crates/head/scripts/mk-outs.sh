@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+
+ROOT="./tests/inputs"
+FILES="$ROOT/empty.txt $ROOT/one.txt $ROOT/two.txt $ROOT/three.txt $ROOT/twelve.txt"
+OUT_DIR="tests/expected"
+
+[[ ! -d "$OUT_DIR" ]] && mkdir -p "$OUT_DIR"
+
+for FILE in $FILES; do
+    BASENAME=$(basename "$FILE")
+    head      $FILE > ${OUT_DIR}/${BASENAME}.out
+    head -n 2 $FILE > ${OUT_DIR}/${BASENAME}.n2.out
+    head -n 4 $FILE > ${OUT_DIR}/${BASENAME}.n4.out
+    head -c 1 $FILE > ${OUT_DIR}/${BASENAME}.c1.out
+    head -c 2 $FILE > ${OUT_DIR}/${BASENAME}.c2.out
+    head -c 4 $FILE > ${OUT_DIR}/${BASENAME}.c4.out
+    head -n -2 $FILE > ${OUT_DIR}/${BASENAME}.n-2.out
+    head -n -4 $FILE > ${OUT_DIR}/${BASENAME}.n-4.out
+    head -c -2 $FILE > ${OUT_DIR}/${BASENAME}.c-2.out
+    head -c -4 $FILE > ${OUT_DIR}/${BASENAME}.c-4.out
+done
+
+head      $FILES > ${OUT_DIR}/all.out
+head -n 2 $FILES > ${OUT_DIR}/all.n2.out
+head -n 4 $FILES > ${OUT_DIR}/all.n4.out
+head -c 1 $FILES > ${OUT_DIR}/all.c1.out
+head -c 2 $FILES > ${OUT_DIR}/all.c2.out
+head -c 4 $FILES > ${OUT_DIR}/all.c4.out
+head -n -2 $FILES > ${OUT_DIR}/all.n-2.out
+head -c -4 $FILES > ${OUT_DIR}/all.c-4.out
+
+ONE="$ROOT/one.txt"
+ZERO="$ROOT/zero.bin"
+
+head -q      $FILES > ${OUT_DIR}/all.q.out
+head -v      $ONE   > ${OUT_DIR}/one.txt.v.out
+head -v    < $ONE   > ${OUT_DIR}/one.txt.v.stdin.out
+head -z -n 2  $ZERO > ${OUT_DIR}/zero.bin.z.n2.out
+head -z -n -2 $ZERO > ${OUT_DIR}/zero.bin.z.n-2.out
+head -z       $ZERO > ${OUT_DIR}/zero.bin.z.out
+head -n 1     $ZERO > ${OUT_DIR}/zero.bin.n1.out
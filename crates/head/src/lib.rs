@@ -1,59 +1,211 @@
 #![allow(dead_code, clippy::missing_errors_doc, clippy::missing_panics_doc)]
-use std::fs::File;
-use std::io::{self, BufRead, BufReader, Read};
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read, Write};
 
 use anyhow::Result;
-use clap::{value_parser, Arg, Command};
+use clap::{Arg, ArgAction, Command};
 use indoc::indoc;
+use roarutils_core::{open, parse_size, ParseSizeError, Reporter};
 
 #[derive(Debug)]
 pub struct Config {
     files: Vec<String>,
-    lines: u64,
-    bytes: Option<u64>,
+    lines: Count,
+    bytes: Option<Count>,
+    headers: bool,
+    delimiter: u8,
+}
+
+/// How much of each file to print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Count {
+    /// The first NUM lines or bytes.
+    First(u64),
+    /// All but the last NUM lines or bytes, given with a leading '-'.
+    AllButLast(u64),
 }
 
-pub fn run(config: &Config) -> Result<()> {
-    let num_files = config.files.len();
+/// Where copying part of a file to standard output failed. Read errors are
+/// reported against the file and skipped, write errors end the run.
+#[derive(Debug)]
+enum CopyError {
+    Read(io::Error),
+    Write(io::Error),
+}
 
-    for (file_num, filename) in config.files.iter().enumerate() {
-        match open(filename) {
-            Err(_) => {
-                eprintln!("head: cannot open '{filename}' for reading: No such file or directory");
+/// Returns the exit status: `0` if every file was read, `1` if any could not
+/// be opened or read. Such files are reported and skipped.
+///
+/// # Errors
+///
+/// Will return `Err` if writing to standard output fails.
+pub fn run(config: &Config) -> Result<i32> {
+    let mut out = io::stdout().lock();
+    let mut first_header = true;
+    let mut reporter = Reporter::new("head");
+
+    for filename in &config.files {
+        let file = match open(filename) {
+            // GNU head opens a directory and only fails when reading it, so
+            // its header is still printed.
+            Err(err) if err.kind() != io::ErrorKind::IsADirectory => {
+                out.flush()?;
+                reporter.error(format!("cannot open '{filename}' for reading"), &err);
+                continue;
+            }
+            file => file,
+        };
+        if config.headers {
+            let name = if filename == "-" {
+                "standard input"
+            } else {
+                filename
+            };
+            writeln!(
+                out,
+                "{}==> {name} <==",
+                if first_header { "" } else { "\n" }
+            )?;
+            first_header = false;
+        }
+        let result = file
+            .map_err(CopyError::Read)
+            .and_then(|mut file| head_file(&mut file, &mut out, config));
+        match result {
+            Ok(()) => {}
+            Err(CopyError::Read(err)) => {
+                out.flush()?;
+                reporter.error(format!("error reading '{filename}'"), &err);
             }
-            Ok(mut file) => {
-                if num_files > 1 {
-                    println!("{}==> {filename} <==", if file_num > 0 { "\n" } else { "" });
-                }
-                if let Some(num_bytes) = config.bytes {
-                    let mut handle = file.take(num_bytes);
-                    let mut buffer = vec![0; usize::try_from(num_bytes).expect("integer overflow")];
-                    let n = handle.read(&mut buffer)?;
-                    print!("{}", String::from_utf8_lossy(&buffer[..n]));
-                } else {
-                    let mut line = String::new();
-                    for _ in 0..config.lines {
-                        let bytes = file.read_line(&mut line)?;
-                        if bytes == 0 {
-                            break;
-                        }
-                        print!("{line}");
-                        line.clear();
-                    }
-                }
+            Err(CopyError::Write(err)) => return Err(err.into()),
+        }
+    }
+    out.flush()?;
+    Ok(reporter.status())
+}
+
+fn head_file(
+    file: &mut impl BufRead,
+    out: &mut impl Write,
+    config: &Config,
+) -> Result<(), CopyError> {
+    match (config.bytes, config.lines) {
+        (Some(Count::First(num_bytes)), _) => print_first_bytes(file, out, num_bytes),
+        (Some(Count::AllButLast(num_bytes)), _) => print_all_but_last_bytes(file, out, num_bytes),
+        (None, Count::First(num_lines)) => {
+            print_first_lines(file, out, num_lines, config.delimiter)
+        }
+        (None, Count::AllButLast(num_lines)) => {
+            print_all_but_last_lines(file, out, num_lines, config.delimiter)
+        }
+    }
+}
+
+/// Copies up to `num` bytes straight from the reader's buffer to `out`.
+fn print_first_bytes(
+    file: &mut impl BufRead,
+    out: &mut impl Write,
+    num: u64,
+) -> Result<(), CopyError> {
+    let mut remaining = num;
+    while remaining > 0 {
+        let buf = match file.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(CopyError::Read(e)),
+        };
+        let len = usize::try_from(remaining).map_or(buf.len(), |n| n.min(buf.len()));
+        out.write_all(&buf[..len]).map_err(CopyError::Write)?;
+        file.consume(len);
+        remaining -= len as u64;
+    }
+    Ok(())
+}
+
+fn print_first_lines(
+    file: &mut impl BufRead,
+    out: &mut impl Write,
+    num: u64,
+    delimiter: u8,
+) -> Result<(), CopyError> {
+    let mut line = Vec::new();
+    for _ in 0..num {
+        if file
+            .read_until(delimiter, &mut line)
+            .map_err(CopyError::Read)?
+            == 0
+        {
+            break;
+        }
+        out.write_all(&line).map_err(CopyError::Write)?;
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Holds back the most recent `num` lines in a ring buffer, writing each
+/// older line as soon as it is known not to be one of the last `num`.
+fn print_all_but_last_lines(
+    file: &mut impl BufRead,
+    out: &mut impl Write,
+    num: u64,
+    delimiter: u8,
+) -> Result<(), CopyError> {
+    let num = usize::try_from(num).unwrap_or(usize::MAX);
+    let mut ring: VecDeque<Vec<u8>> = VecDeque::new();
+    let mut line = Vec::new();
+    while file
+        .read_until(delimiter, &mut line)
+        .map_err(CopyError::Read)?
+        > 0
+    {
+        ring.push_back(line);
+        line = Vec::new();
+        if ring.len() > num {
+            if let Some(oldest) = ring.pop_front() {
+                out.write_all(&oldest).map_err(CopyError::Write)?;
+                // Reuse the allocation of the line that left the buffer.
+                line = oldest;
+                line.clear();
             }
         }
     }
     Ok(())
 }
 
-fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
+/// Holds back the most recent `num` bytes in a ring buffer, writing older
+/// bytes as each chunk of input arrives.
+fn print_all_but_last_bytes(
+    file: &mut impl Read,
+    out: &mut impl Write,
+    num: u64,
+) -> Result<(), CopyError> {
+    let num = usize::try_from(num).unwrap_or(usize::MAX);
+    let mut ring: VecDeque<u8> = VecDeque::new();
+    let mut chunk = [0; 8192];
+    loop {
+        let bytes = match file.read(&mut chunk) {
+            Ok(0) => return Ok(()),
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(CopyError::Read(e)),
+        };
+        ring.extend(&chunk[..bytes]);
+        if ring.len() > num {
+            let excess = ring.len() - num;
+            let (front, back) = ring.as_slices();
+            let from_front = excess.min(front.len());
+            out.write_all(&front[..from_front])
+                .and_then(|()| out.write_all(&back[..excess - from_front]))
+                .map_err(CopyError::Write)?;
+            ring.drain(..excess);
+        }
     }
 }
 
+#[allow(clippy::too_many_lines)]
 #[must_use]
 pub fn get_args() -> Config {
     let matches = Command::new("head")
@@ -98,7 +250,8 @@ pub fn get_args() -> Config {
                       with the leading '-', print all but the last
                       NUM bytes of each file
                 "})
-                .value_parser(value_parser!(u64).range(1..))
+                .value_parser(parse_count)
+                .allow_hyphen_values(true)
                 .conflicts_with("lines"),
         )
         .arg(
@@ -111,9 +264,34 @@ pub fn get_args() -> Config {
                       with the leading '-', print all but the last
                       NUM lines of each file
                 "})
-                .value_parser(value_parser!(u64).range(1..))
+                .value_parser(parse_count)
+                .allow_hyphen_values(true)
                 .default_value("10"),
         )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .visible_alias("silent")
+                .action(ArgAction::SetTrue)
+                .overrides_with("verbose")
+                .help("never print headers giving file names"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .overrides_with("quiet")
+                .help("always print headers giving file names"),
+        )
+        .arg(
+            Arg::new("zero_terminated")
+                .short('z')
+                .long("zero-terminated")
+                .action(ArgAction::SetTrue)
+                .help("line delimiter is NUL, not newline"),
+        )
         .get_matches();
 
     let files: Vec<String> = matches
@@ -125,9 +303,27 @@ pub fn get_args() -> Config {
     let bytes = matches.get_one("bytes").copied();
     let lines = matches.get_one("lines").copied().expect("lines invalid");
 
+    let headers = matches.get_flag("verbose") || (!matches.get_flag("quiet") && files.len() > 1);
+    let delimiter = if matches.get_flag("zero_terminated") {
+        b'\0'
+    } else {
+        b'\n'
+    };
+
     Config {
         files,
         lines,
         bytes,
+        headers,
+        delimiter,
     }
 }
+
+/// Parses NUM for `-c` and `-n`, where a leading '-' selects all but the
+/// last NUM.
+fn parse_count(value: &str) -> Result<Count, ParseSizeError> {
+    value.strip_prefix('-').map_or_else(
+        || parse_size(value).map(Count::First),
+        |num| parse_size(num).map(Count::AllButLast),
+    )
+}
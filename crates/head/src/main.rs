@@ -1,6 +1,9 @@
 fn main() {
-    if let Err(e) = head::run(&head::get_args()) {
-        eprintln!("{e}");
-        std::process::exit(1);
+    match head::run(&head::get_args()) {
+        Ok(status) => std::process::exit(status),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
     }
 }
@@ -15,6 +15,7 @@ const ONE: &str = "./tests/inputs/one.txt";
 const TWO: &str = "./tests/inputs/two.txt";
 const THREE: &str = "./tests/inputs/three.txt";
 const TWELVE: &str = "./tests/inputs/twelve.txt";
+const ZERO: &str = "./tests/inputs/zero.bin";
 
 fn random_string() -> String {
     rand::thread_rng()
@@ -36,13 +37,41 @@ fn gen_bad_file() -> String {
 #[test]
 fn dies_bad_bytes() -> Result<()> {
     let bad = random_string();
-    let expected =
-        format!("invalid value '{bad}' for '--bytes <BYTES>': invalid digit found in string");
+    let expected = format!(
+        "invalid value '{bad}' for '--bytes <BYTES>': invalid (digit found in string|suffix '.*')"
+    );
 
     Command::cargo_bin(PRG)?
         .args(["-c", &bad, EMPTY])
         .assert()
         .failure()
+        .stderr(predicate::str::is_match(expected)?);
+
+    Ok(())
+}
+
+#[test]
+fn dies_bad_suffix() -> Result<()> {
+    let expected = "invalid value '1g' for '--lines <LINES>': invalid suffix 'g'";
+
+    Command::cargo_bin(PRG)?
+        .args(["-n", "1g", EMPTY])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(expected));
+
+    Ok(())
+}
+
+#[test]
+fn dies_too_large() -> Result<()> {
+    let expected =
+        "invalid value '16E' for '--bytes <BYTES>': Value too large for defined data type";
+
+    Command::cargo_bin(PRG)?
+        .args(["-c", "16E", EMPTY])
+        .assert()
+        .failure()
         .stderr(predicate::str::contains(expected));
 
     Ok(())
@@ -68,11 +97,34 @@ fn skips_bad_file() -> Result<()> {
     Command::cargo_bin(PRG)?
         .args([EMPTY, &bad, ONE])
         .assert()
+        .code(1)
         .stderr(predicate::str::is_match(expected)?);
 
     Ok(())
 }
 
+#[test]
+fn skips_directory() -> Result<()> {
+    let dir = "./tests/inputs";
+    for (args, expected_file) in [
+        (["-n", "2"], "tests/expected/one.txt.n2.out"),
+        (["-n", "-2"], "tests/expected/one.txt.n-2.out"),
+        (["-c", "2"], "tests/expected/one.txt.c2.out"),
+        (["-c", "-2"], "tests/expected/one.txt.c-2.out"),
+    ] {
+        let mut expected = format!("==> {dir} <==\n\n==> {ONE} <==\n").into_bytes();
+        expected.extend(fs::read(expected_file)?);
+        Command::cargo_bin(PRG)?
+            .args(args)
+            .args([dir, ONE])
+            .assert()
+            .code(1)
+            .stdout(expected)
+            .stderr(format!("head: error reading '{dir}': Is a directory\n"));
+    }
+    Ok(())
+}
+
 fn run(args: &[&str], expected_file: &str) -> Result<()> {
     let mut file = File::open(expected_file)?;
     let mut buffer = Vec::new();
@@ -339,6 +391,207 @@ fn twelve_c4_stdin() -> Result<()> {
     run_stdin(&["-c", "4"], TWELVE, "tests/expected/twelve.txt.c4.out")
 }
 
+#[test]
+fn empty_n_minus2() -> Result<()> {
+    run(&[EMPTY, "-n", "-2"], "tests/expected/empty.txt.n-2.out")
+}
+
+#[test]
+fn empty_n_minus4() -> Result<()> {
+    run(&[EMPTY, "-n-4"], "tests/expected/empty.txt.n-4.out")
+}
+
+#[test]
+fn empty_c_minus2() -> Result<()> {
+    run(&[EMPTY, "-c", "-2"], "tests/expected/empty.txt.c-2.out")
+}
+
+#[test]
+fn empty_c_minus4() -> Result<()> {
+    run(&[EMPTY, "--bytes=-4"], "tests/expected/empty.txt.c-4.out")
+}
+
+#[test]
+fn empty_n_minus2_stdin() -> Result<()> {
+    run_stdin(&["-n", "-2"], EMPTY, "tests/expected/empty.txt.n-2.out")
+}
+
+#[test]
+fn empty_c_minus4_stdin() -> Result<()> {
+    run_stdin(&["-c", "-4"], EMPTY, "tests/expected/empty.txt.c-4.out")
+}
+
+#[test]
+fn one_n_minus2() -> Result<()> {
+    run(&[ONE, "-n", "-2"], "tests/expected/one.txt.n-2.out")
+}
+
+#[test]
+fn one_n_minus4() -> Result<()> {
+    run(&[ONE, "-n-4"], "tests/expected/one.txt.n-4.out")
+}
+
+#[test]
+fn one_c_minus2() -> Result<()> {
+    run(&[ONE, "-c", "-2"], "tests/expected/one.txt.c-2.out")
+}
+
+#[test]
+fn one_c_minus4() -> Result<()> {
+    run(&[ONE, "--bytes=-4"], "tests/expected/one.txt.c-4.out")
+}
+
+#[test]
+fn one_n_minus2_stdin() -> Result<()> {
+    run_stdin(&["-n", "-2"], ONE, "tests/expected/one.txt.n-2.out")
+}
+
+#[test]
+fn one_c_minus4_stdin() -> Result<()> {
+    run_stdin(&["-c", "-4"], ONE, "tests/expected/one.txt.c-4.out")
+}
+
+#[test]
+fn two_n_minus2() -> Result<()> {
+    run(&[TWO, "-n", "-2"], "tests/expected/two.txt.n-2.out")
+}
+
+#[test]
+fn two_n_minus4() -> Result<()> {
+    run(&[TWO, "-n-4"], "tests/expected/two.txt.n-4.out")
+}
+
+#[test]
+fn two_c_minus2() -> Result<()> {
+    run(&[TWO, "-c", "-2"], "tests/expected/two.txt.c-2.out")
+}
+
+#[test]
+fn two_c_minus4() -> Result<()> {
+    run(&[TWO, "--bytes=-4"], "tests/expected/two.txt.c-4.out")
+}
+
+#[test]
+fn two_n_minus2_stdin() -> Result<()> {
+    run_stdin(&["-n", "-2"], TWO, "tests/expected/two.txt.n-2.out")
+}
+
+#[test]
+fn two_c_minus4_stdin() -> Result<()> {
+    run_stdin(&["-c", "-4"], TWO, "tests/expected/two.txt.c-4.out")
+}
+
+#[test]
+fn three_n_minus2() -> Result<()> {
+    run(&[THREE, "-n", "-2"], "tests/expected/three.txt.n-2.out")
+}
+
+#[test]
+fn three_n_minus4() -> Result<()> {
+    run(&[THREE, "-n-4"], "tests/expected/three.txt.n-4.out")
+}
+
+#[test]
+fn three_c_minus2() -> Result<()> {
+    run(&[THREE, "-c", "-2"], "tests/expected/three.txt.c-2.out")
+}
+
+#[test]
+fn three_c_minus4() -> Result<()> {
+    run(&[THREE, "--bytes=-4"], "tests/expected/three.txt.c-4.out")
+}
+
+#[test]
+fn three_n_minus2_stdin() -> Result<()> {
+    run_stdin(&["-n", "-2"], THREE, "tests/expected/three.txt.n-2.out")
+}
+
+#[test]
+fn three_c_minus4_stdin() -> Result<()> {
+    run_stdin(&["-c", "-4"], THREE, "tests/expected/three.txt.c-4.out")
+}
+
+#[test]
+fn twelve_n_minus2() -> Result<()> {
+    run(&[TWELVE, "-n", "-2"], "tests/expected/twelve.txt.n-2.out")
+}
+
+#[test]
+fn twelve_n_minus4() -> Result<()> {
+    run(&[TWELVE, "-n-4"], "tests/expected/twelve.txt.n-4.out")
+}
+
+#[test]
+fn twelve_c_minus2() -> Result<()> {
+    run(&[TWELVE, "-c", "-2"], "tests/expected/twelve.txt.c-2.out")
+}
+
+#[test]
+fn twelve_c_minus4() -> Result<()> {
+    run(&[TWELVE, "--bytes=-4"], "tests/expected/twelve.txt.c-4.out")
+}
+
+#[test]
+fn twelve_n_minus2_stdin() -> Result<()> {
+    run_stdin(&["-n", "-2"], TWELVE, "tests/expected/twelve.txt.n-2.out")
+}
+
+#[test]
+fn twelve_c_minus4_stdin() -> Result<()> {
+    run_stdin(&["-c", "-4"], TWELVE, "tests/expected/twelve.txt.c-4.out")
+}
+
+#[test]
+fn twelve_suffixes() -> Result<()> {
+    let expected = fs::read(TWELVE)?;
+    for args in [["-c", "1b"], ["-c", "1kB"], ["-n", "1K"], ["-n", "1MiB"]] {
+        Command::cargo_bin(PRG)?
+            .args(args)
+            .arg(TWELVE)
+            .assert()
+            .success()
+            .stdout(expected.clone());
+    }
+    Command::cargo_bin(PRG)?
+        .args(["-c", "-1K", TWELVE])
+        .assert()
+        .success()
+        .stdout("");
+    Ok(())
+}
+
+#[test]
+fn bytes_are_not_decoded() -> Result<()> {
+    let input = fs::read(ONE)?;
+    for num in 1..=4 {
+        Command::cargo_bin(PRG)?
+            .args(["-c", &num.to_string(), ONE])
+            .assert()
+            .success()
+            .stdout(input[..num].to_vec());
+    }
+    Ok(())
+}
+
+#[test]
+fn bytes_stream() -> Result<()> {
+    let input: Vec<u8> = (0..=u8::MAX).cycle().take(1_000_000).collect();
+    Command::cargo_bin(PRG)?
+        .args(["-c", "10G"])
+        .write_stdin(input.clone())
+        .assert()
+        .success()
+        .stdout(input.clone());
+
+    Command::cargo_bin(PRG)?
+        .args(["-c", "999999"])
+        .write_stdin(input.clone())
+        .assert()
+        .success()
+        .stdout(input[..999_999].to_vec());
+    Ok(())
+}
+
 #[test]
 fn multiple_files() -> Result<()> {
     run(&[EMPTY, ONE, TWO, THREE, TWELVE], "tests/expected/all.out")
@@ -383,3 +636,111 @@ fn multiple_files_c4() -> Result<()> {
         "tests/expected/all.c4.out",
     )
 }
+
+#[test]
+fn multiple_files_n_minus2() -> Result<()> {
+    run(
+        &["-n", "-2", EMPTY, ONE, TWO, THREE, TWELVE],
+        "tests/expected/all.n-2.out",
+    )
+}
+
+#[test]
+fn multiple_files_c_minus4() -> Result<()> {
+    run(
+        &[EMPTY, ONE, TWO, THREE, TWELVE, "-c", "-4"],
+        "tests/expected/all.c-4.out",
+    )
+}
+
+#[test]
+fn all_but_last_stream() -> Result<()> {
+    let lines: Vec<String> = (1..=100_000).map(|n| n.to_string()).collect();
+    let input = lines.join("\n") + "\n";
+    let expected = lines[..99_990].join("\n") + "\n";
+    Command::cargo_bin(PRG)?
+        .args(["-n", "-10"])
+        .write_stdin(input.as_bytes())
+        .assert()
+        .success()
+        .stdout(expected);
+
+    let expected = &input.as_bytes()[..input.len() - 5000];
+    Command::cargo_bin(PRG)?
+        .args(["-c", "-5000"])
+        .write_stdin(input.as_bytes())
+        .assert()
+        .success()
+        .stdout(expected.to_vec());
+
+    Ok(())
+}
+
+#[test]
+fn multiple_files_quiet() -> Result<()> {
+    run(
+        &["-q", EMPTY, ONE, TWO, THREE, TWELVE],
+        "tests/expected/all.q.out",
+    )?;
+    run(
+        &["--silent", EMPTY, ONE, TWO, THREE, TWELVE],
+        "tests/expected/all.q.out",
+    )
+}
+
+#[test]
+fn one_verbose() -> Result<()> {
+    run(&["-v", ONE], "tests/expected/one.txt.v.out")?;
+    run(&["-q", "--verbose", ONE], "tests/expected/one.txt.v.out")
+}
+
+#[test]
+fn one_verbose_stdin() -> Result<()> {
+    run_stdin(&["-v"], ONE, "tests/expected/one.txt.v.stdin.out")
+}
+
+#[test]
+fn multiple_files_verbose_then_quiet() -> Result<()> {
+    run(
+        &["-v", "-q", EMPTY, ONE, TWO, THREE, TWELVE],
+        "tests/expected/all.q.out",
+    )
+}
+
+#[test]
+fn first_header_after_bad_file() -> Result<()> {
+    let bad = gen_bad_file();
+    Command::cargo_bin(PRG)?
+        .args([&bad, EMPTY])
+        .assert()
+        .stdout(format!("==> {EMPTY} <==\n"));
+    Ok(())
+}
+
+#[test]
+fn zero() -> Result<()> {
+    run(&["-z", ZERO], "tests/expected/zero.bin.z.out")
+}
+
+#[test]
+fn zero_n2() -> Result<()> {
+    run(&["-z", "-n", "2", ZERO], "tests/expected/zero.bin.z.n2.out")
+}
+
+#[test]
+fn zero_n_minus2() -> Result<()> {
+    run(
+        &["--zero-terminated", "-n", "-2", ZERO],
+        "tests/expected/zero.bin.z.n-2.out",
+    )
+}
+
+#[test]
+fn zero_n2_stdin() -> Result<()> {
+    run_stdin(&["-z", "-n", "2"], ZERO, "tests/expected/zero.bin.z.n2.out")
+}
+
+#[test]
+fn zero_without_z() -> Result<()> {
+    run(&["-n", "1", ZERO], "tests/expected/zero.bin.n1.out")
+}
@@ -1,3 +1,7 @@
 fn main() {
-    std::process::abort();
+    std::process::exit(true_false::run(
+        "false",
+        "Exit with a status code indicating failure.",
+        1,
+    ));
 }
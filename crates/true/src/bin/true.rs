@@ -1 +1,7 @@
-const fn main() {}
+fn main() {
+    std::process::exit(true_false::run(
+        "true",
+        "Exit with a status code indicating success.",
+        0,
+    ));
+}
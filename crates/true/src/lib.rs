@@ -0,0 +1,45 @@
+//! The shared implementation of `true` and `false`, which differ only in
+//! their description and exit status.
+
+use std::env;
+use std::ffi::OsString;
+use std::io::{self, Write};
+
+/// Returns the exit status of the utility `name`, which is `status` unless
+/// its help or version information cannot be written.
+///
+/// Like GNU `true` and `false`, all arguments are ignored, except that
+/// `--help` and `--version` are honoured when given as the only argument.
+#[must_use]
+pub fn run(name: &str, description: &str, status: i32) -> i32 {
+    let args: Vec<OsString> = env::args_os().skip(1).collect();
+    let text = match args.as_slice() {
+        [arg] if arg == "--help" => help(name, description),
+        [arg] if arg == "--version" => format!("{name} {}\n", env!("CARGO_PKG_VERSION")),
+        _ => return status,
+    };
+    let mut stdout = io::stdout().lock();
+    match stdout
+        .write_all(text.as_bytes())
+        .and_then(|()| stdout.flush())
+    {
+        Ok(()) => status,
+        Err(_) => 1,
+    }
+}
+
+fn help(name: &str, description: &str) -> String {
+    format!(
+        "Usage: {name} [ignored command line arguments]
+  or:  {name} OPTION
+{description}
+
+      --help        display this help and exit
+      --version     output version information and exit
+
+GNU coreutils online help: <https://www.gnu.org/software/coreutils/>
+Full documentation <https://www.gnu.org/software/coreutils/{name}>
+or available locally via: info '(coreutils) {name} invocation'
+"
+    )
+}
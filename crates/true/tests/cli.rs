@@ -4,11 +4,66 @@ use assert_cmd::Command;
 #[test]
 fn true_ok() {
     let mut cmd = Command::cargo_bin("true").unwrap();
-    cmd.assert().success();
+    cmd.assert().code(0).stdout("").stderr("");
 }
 
 #[test]
 fn false_not_ok() {
     let mut cmd = Command::cargo_bin("false").unwrap();
-    cmd.assert().failure();
+    cmd.assert().code(1).stdout("").stderr("");
+}
+
+#[test]
+fn true_ignores_args() {
+    let mut cmd = Command::cargo_bin("true").unwrap();
+    cmd.args(["--bogus", "-x", "--help", "--version"])
+        .assert()
+        .code(0)
+        .stdout("");
+}
+
+#[test]
+fn false_ignores_args() {
+    let mut cmd = Command::cargo_bin("false").unwrap();
+    cmd.args(["--bogus", "-x", "--help", "--version"])
+        .assert()
+        .code(1)
+        .stdout("");
+}
+
+#[test]
+fn true_help() {
+    let mut cmd = Command::cargo_bin("true").unwrap();
+    cmd.arg("--help")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::starts_with(
+            "Usage: true [ignored command line arguments]\n",
+        ));
+}
+
+#[test]
+fn false_help() {
+    let mut cmd = Command::cargo_bin("false").unwrap();
+    cmd.arg("--help")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::starts_with(
+            "Usage: false [ignored command line arguments]\n",
+        ));
+}
+
+#[test]
+fn true_version() {
+    let mut cmd = Command::cargo_bin("true").unwrap();
+    cmd.arg("--version").assert().code(0).stdout("true 0.1.0\n");
+}
+
+#[test]
+fn false_version() {
+    let mut cmd = Command::cargo_bin("false").unwrap();
+    cmd.arg("--version")
+        .assert()
+        .code(1)
+        .stdout("false 0.1.0\n");
 }
@@ -12,6 +12,11 @@ echo -ne "a\nb"      > $ROOT/t3.txt
 echo -ne "a\na\nb"   > $ROOT/t4.txt
 echo -ne "b\na\na\n" > $ROOT/t5.txt
 echo -ne "a\nb\nc\n" > $ROOT/t6.txt
+echo -ne "a\na \na\t\na\na\n" > $ROOT/t7.txt
+echo -ne "a\r\na\r\nb\r\nb\nb" > $ROOT/t8.txt
+echo -ne "a \na " > $ROOT/t9.txt
+echo -ne "a\0a\0b\nc\0b\nc\0b\nd\0e" > $ROOT/zero.bin
+echo -ne "caf\xe9\ncaf\xe9\nCAF\xe9\nna\xefve" > $ROOT/latin1.bin
 
 for FILE in $ROOT/*.txt; do
     BASENAME=$(basename "$FILE")
@@ -19,4 +24,51 @@ for FILE in $ROOT/*.txt; do
     uniq -c   $FILE > ${OUT_DIR}/${BASENAME}.c.out
     uniq    < $FILE > ${OUT_DIR}/${BASENAME}.stdin.out
     uniq -c < $FILE > ${OUT_DIR}/${BASENAME}.stdin.c.out
+    uniq -d   $FILE > ${OUT_DIR}/${BASENAME}.d.out
+    uniq -D   $FILE > ${OUT_DIR}/${BASENAME}.D.out
+    uniq -u   $FILE > ${OUT_DIR}/${BASENAME}.u.out
 done
+
+THREE="$ROOT/three.txt"
+uniq -dc   $THREE > ${OUT_DIR}/three.txt.dc.out
+uniq -uc   $THREE > ${OUT_DIR}/three.txt.uc.out
+uniq -du   $THREE > ${OUT_DIR}/three.txt.du.out
+uniq -Du   $THREE > ${OUT_DIR}/three.txt.Du.out
+uniq --all-repeated=prepend  $THREE > ${OUT_DIR}/three.txt.D-prepend.out
+uniq --all-repeated=separate $THREE > ${OUT_DIR}/three.txt.D-separate.out
+uniq --group          $THREE > ${OUT_DIR}/three.txt.group.out
+uniq --group=separate $THREE > ${OUT_DIR}/three.txt.group-separate.out
+uniq --group=prepend  $THREE > ${OUT_DIR}/three.txt.group-prepend.out
+uniq --group=append   $THREE > ${OUT_DIR}/three.txt.group-append.out
+uniq --group=both     $THREE > ${OUT_DIR}/three.txt.group-both.out
+uniq --group=both < "$ROOT/empty.txt" > ${OUT_DIR}/empty.txt.group-both.out
+
+FIELDS="$ROOT/fields.txt"
+uniq -f 1            $FIELDS > ${OUT_DIR}/fields.txt.f1.out
+uniq -s 2            $FIELDS > ${OUT_DIR}/fields.txt.s2.out
+uniq -w 3            $FIELDS > ${OUT_DIR}/fields.txt.w3.out
+uniq -i              $FIELDS > ${OUT_DIR}/fields.txt.i.out
+uniq -f 1 -i         $FIELDS > ${OUT_DIR}/fields.txt.f1i.out
+uniq -f 1 -w 6       $FIELDS > ${OUT_DIR}/fields.txt.f1w6.out
+uniq -f 1 -s 1 -w 5  $FIELDS > ${OUT_DIR}/fields.txt.f1s1w5.out
+uniq -c -f 1 -i      $FIELDS > ${OUT_DIR}/fields.txt.cf1i.out
+uniq -D -f 1 -w 6 -i $FIELDS > ${OUT_DIR}/fields.txt.Df1w6i.out
+uniq --group -f 1 -i $FIELDS > ${OUT_DIR}/fields.txt.group-f1i.out
+
+SKIP="$ROOT/skip.txt"
+uniq -s 1 $SKIP > ${OUT_DIR}/skip.txt.s1.out
+uniq -w 1 $SKIP > ${OUT_DIR}/skip.txt.w1.out
+uniq -c -f 1 $SKIP > ${OUT_DIR}/skip.txt.cf1.out
+
+ZERO="$ROOT/zero.bin"
+uniq                            $ZERO > ${OUT_DIR}/zero.bin.out
+uniq -z                         $ZERO > ${OUT_DIR}/zero.bin.z.out
+uniq -zc                        $ZERO > ${OUT_DIR}/zero.bin.zc.out
+uniq -zu                        $ZERO > ${OUT_DIR}/zero.bin.zu.out
+uniq -z --all-repeated=separate $ZERO > ${OUT_DIR}/zero.bin.zD-separate.out
+uniq -z -f 1 --group=both       $ZERO > ${OUT_DIR}/zero.bin.zf1-group-both.out
+
+LATIN1="$ROOT/latin1.bin"
+uniq    $LATIN1 > ${OUT_DIR}/latin1.bin.out
+uniq -c $LATIN1 > ${OUT_DIR}/latin1.bin.c.out
+uniq -i $LATIN1 > ${OUT_DIR}/latin1.bin.i.out
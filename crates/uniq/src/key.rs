@@ -0,0 +1,121 @@
+//! The part of each line that uniq compares.
+
+/// Selects and compares the comparison key of a line: fields are skipped
+/// before characters, then at most `check_chars` characters are compared.
+///
+/// As in GNU uniq, a field is a run of blanks (spaces and/or tabs, or
+/// newlines within NUL-terminated lines) followed by non-blank characters,
+/// and characters are counted in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Key {
+    pub skip_fields: usize,
+    pub skip_chars: usize,
+    pub check_chars: Option<usize>,
+    pub ignore_case: bool,
+}
+
+impl Key {
+    /// Returns the part of `line` that is compared.
+    #[must_use]
+    pub fn extract<'a>(&self, line: &'a [u8]) -> &'a [u8] {
+        let mut start = 0;
+        for _ in 0..self.skip_fields {
+            if start == line.len() {
+                break;
+            }
+            start += line[start..].iter().take_while(|b| is_blank(**b)).count();
+            start += line[start..].iter().take_while(|b| !is_blank(**b)).count();
+        }
+        let start = line.len().min(start.saturating_add(self.skip_chars));
+        let end = self
+            .check_chars
+            .map_or(line.len(), |n| line.len().min(start.saturating_add(n)));
+        &line[start..end]
+    }
+
+    /// Whether two lines have equal keys.
+    #[must_use]
+    pub fn matches(&self, a: &[u8], b: &[u8]) -> bool {
+        let (a, b) = (self.extract(a), self.extract(b));
+        if self.ignore_case {
+            a.eq_ignore_ascii_case(b)
+        } else {
+            a == b
+        }
+    }
+}
+
+const fn is_blank(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract() {
+        let line = b"  one\ttwo three";
+        assert_eq!(Key::default().extract(line), line);
+
+        let key = Key {
+            skip_fields: 1,
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"\ttwo three");
+
+        let key = Key {
+            skip_fields: 2,
+            skip_chars: 1,
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"three");
+
+        let key = Key {
+            skip_chars: 3,
+            check_chars: Some(2),
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"ne");
+
+        let key = Key {
+            skip_fields: 5,
+            skip_chars: 5,
+            check_chars: Some(usize::MAX),
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"");
+
+        let key = Key {
+            skip_fields: usize::MAX,
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"");
+
+        let key = Key {
+            skip_fields: 1,
+            skip_chars: usize::MAX,
+            ..Key::default()
+        };
+        assert_eq!(key.extract(line), b"");
+        assert!(key.matches(b"a b", b"a c"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let key = Key {
+            skip_fields: 1,
+            ignore_case: true,
+            ..Key::default()
+        };
+        assert!(key.matches(b"1 apple", b"2 APPLE"));
+        assert!(!key.matches(b"1 apple", b"2  apple"));
+        let key = Key {
+            skip_fields: 2,
+            ignore_case: true,
+            ..Key::default()
+        };
+        assert!(key.matches(b"1\n2 apple", b"3 4 APPLE"));
+        assert!(!Key::default().matches(b"apple", b"Apple"));
+    }
+}
@@ -1,9 +1,15 @@
+pub mod key;
+
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, Write};
+use std::io::{self, BufRead, Write};
+use std::num::{IntErrorKind, ParseIntError};
 
 use anyhow::{anyhow, Result};
-use clap::{Arg, ArgAction, Command};
+use clap::{value_parser, Arg, ArgAction, Command, ValueEnum};
 use indoc::indoc;
+use roarutils_core::{format_error, open};
+
+use crate::key::Key;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -11,56 +17,149 @@ pub struct Args {
     in_file: String,
     out_file: Option<String>,
     count: bool,
+    repeated: bool,
+    all_repeated: Option<Delimit>,
+    unique: bool,
+    key: Key,
+    delimiter: u8,
+    group: Option<Grouping>,
+}
+
+/// How `--all-repeated` separates groups of duplicate lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Delimit {
+    /// Do not separate groups.
+    None,
+    /// Print an empty line before each group.
+    Prepend,
+    /// Print an empty line between groups.
+    Separate,
+}
+
+/// Where `--group` prints an empty line around groups of lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Grouping {
+    /// Between groups.
+    Separate,
+    /// Before each group.
+    Prepend,
+    /// After each group.
+    Append,
+    /// Before and after each group.
+    Both,
 }
 
 /// # Errors
 ///
 /// Will return `Err` if `Read` of `Write` operations fail.
 pub fn run(args: &Args) -> Result<()> {
-    let mut file = open(&args.in_file).map_err(|e| anyhow!("{}: {e}", args.in_file))?;
+    let in_file = &args.in_file;
+    let mut file = open(in_file).map_err(|e| anyhow!(format_error("uniq", in_file, &e)))?;
 
     let mut out_file: Box<dyn Write> = match &args.out_file {
-        Some(out_name) => Box::new(File::create(out_name)?),
+        Some(out_name) => Box::new(
+            File::create(out_name).map_err(|e| anyhow!(format_error("uniq", out_name, &e)))?,
+        ),
         _ => Box::new(io::stdout()),
     };
 
-    let mut print = |num: u64, text: &str| -> Result<()> {
-        if num > 0 {
-            if args.count {
-                write!(out_file, "{num:>7} {text}")?;
-            } else {
-                write!(out_file, "{text}")?;
-            }
-        };
-        Ok(())
-    };
-
-    let (mut line, mut previous) = (String::new(), String::new());
+    let delimiter = args.delimiter;
+    let (mut line, mut previous) = (Vec::new(), Vec::new());
     let mut count: u64 = 0;
+    let mut printed_group = false;
     loop {
-        let bytes = file.read_line(&mut line)?;
+        line.clear();
+        let bytes = file.read_until(delimiter, &mut line).map_err(|e| {
+            anyhow!(format_error(
+                "uniq",
+                format!("error reading '{in_file}'"),
+                &e
+            ))
+        })?;
         if bytes == 0 {
             break;
         }
 
-        if line.trim_end() != previous.trim_end() {
-            print(count, &previous)?;
-            previous.clone_from(&line);
-            count = 0;
+        if count > 0
+            && args.key.matches(
+                strip_delimiter(&line, delimiter),
+                strip_delimiter(&previous, delimiter),
+            )
+        {
+            count += 1;
+            // Every line of a repeated group is printed as soon as the
+            // group is known to repeat.
+            if let Some(delimit) = args.all_repeated {
+                if count == 2 {
+                    if delimit == Delimit::Prepend
+                        || (delimit == Delimit::Separate && printed_group)
+                    {
+                        out_file.write_all(&[delimiter])?;
+                    }
+                    printed_group = true;
+                    if !args.unique {
+                        write_line(&mut out_file, &previous, delimiter)?;
+                    }
+                }
+                write_line(&mut out_file, &line, delimiter)?;
+            }
+            if args.group.is_some() {
+                write_line(&mut out_file, &line, delimiter)?;
+            }
+            continue;
         }
-        count += 1;
-        line.clear();
+        if let Some(group) = args.group {
+            // Adjacent groups share one separator, whatever the method.
+            if count > 0 || matches!(group, Grouping::Prepend | Grouping::Both) {
+                out_file.write_all(&[delimiter])?;
+            }
+            write_line(&mut out_file, &line, delimiter)?;
+        }
+        print_group(&mut out_file, args, count, &previous)?;
+        std::mem::swap(&mut previous, &mut line);
+        count = 1;
+    }
+    if count > 0 && matches!(args.group, Some(Grouping::Append | Grouping::Both)) {
+        out_file.write_all(&[delimiter])?;
+    }
+    print_group(&mut out_file, args, count, &previous)?;
+    out_file.flush()?;
+
+    Ok(())
+}
+
+/// Prints the first line of a group of `count` adjacent matching lines,
+/// unless the output mode filters the group out.
+fn print_group(out: &mut impl Write, args: &Args, count: u64, line: &[u8]) -> io::Result<()> {
+    if count == 0 || args.all_repeated.is_some() || args.group.is_some() {
+        return Ok(());
+    }
+    if (count == 1 && args.repeated) || (count > 1 && args.unique) {
+        return Ok(());
     }
-    print(count, &previous)?;
-    if !previous.is_empty() && !previous.ends_with('\n') {
-        writeln!(out_file)?;
+    if args.count {
+        write!(out, "{count:>7} ")?;
     }
+    write_line(out, line, args.delimiter)
+}
+
+/// Returns the line without its terminator. Everything else, including
+/// trailing blanks and a carriage return, is part of the line.
+fn strip_delimiter(line: &[u8], delimiter: u8) -> &[u8] {
+    line.strip_suffix(&[delimiter]).unwrap_or(line)
+}
 
+/// Writes a line, adding the delimiter missing from the end of the input.
+fn write_line(out: &mut impl Write, line: &[u8], delimiter: u8) -> io::Result<()> {
+    out.write_all(line)?;
+    if line.last() != Some(&delimiter) {
+        out.write_all(&[delimiter])?;
+    }
     Ok(())
 }
 
 // FILE has a default value so cannot panic.
-#[allow(clippy::missing_panics_doc)]
+#[allow(clippy::missing_panics_doc, clippy::too_many_lines)]
 #[must_use]
 pub fn get_args() -> Args {
     let matches = Command::new("uniq")
@@ -105,6 +204,93 @@ pub fn get_args() -> Args {
                 .action(ArgAction::SetTrue)
                 .num_args(0),
         )
+        .arg(
+            Arg::new("repeated")
+                .help("only print duplicate lines, one for each group")
+                .short('d')
+                .long("repeated")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("all_repeated_none")
+                .help("print all duplicate lines")
+                .short('D')
+                .action(ArgAction::SetTrue)
+                .conflicts_with("count"),
+        )
+        .arg(
+            Arg::new("all_repeated")
+                .value_name("METHOD")
+                .help("like -D, but allow separating groups with an empty line")
+                .long("all-repeated")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("none")
+                .value_parser(value_parser!(Delimit))
+                .conflicts_with("count"),
+        )
+        .arg(
+            Arg::new("unique")
+                .help("only print unique lines")
+                .short('u')
+                .long("unique")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("skip_fields")
+                .value_name("N")
+                .help("avoid comparing the first N fields")
+                .short('f')
+                .long("skip-fields")
+                .value_parser(parse_count),
+        )
+        .arg(
+            Arg::new("ignore_case")
+                .help("ignore differences in case when comparing")
+                .short('i')
+                .long("ignore-case")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("skip_chars")
+                .value_name("N")
+                .help("avoid comparing the first N characters")
+                .short('s')
+                .long("skip-chars")
+                .value_parser(parse_count),
+        )
+        .arg(
+            Arg::new("check_chars")
+                .value_name("N")
+                .help("compare no more than N characters in lines")
+                .short('w')
+                .long("check-chars")
+                .value_parser(parse_count),
+        )
+        .arg(
+            Arg::new("zero_terminated")
+                .help("line delimiter is NUL, not newline")
+                .short('z')
+                .long("zero-terminated")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("group")
+                .value_name("METHOD")
+                .help("show all items, separating groups with an empty line")
+                .long("group")
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_missing_value("separate")
+                .value_parser(value_parser!(Grouping))
+                .conflicts_with_all([
+                    "count",
+                    "repeated",
+                    "all_repeated_none",
+                    "all_repeated",
+                    "unique",
+                ]),
+        )
         .get_matches();
 
     let in_file: String = matches
@@ -117,15 +303,33 @@ pub fn get_args() -> Args {
         in_file,
         out_file,
         count: matches.get_flag("count"),
+        repeated: matches.get_flag("repeated"),
+        all_repeated: matches.get_one("all_repeated").copied().or_else(|| {
+            matches
+                .get_flag("all_repeated_none")
+                .then_some(Delimit::None)
+        }),
+        unique: matches.get_flag("unique"),
+        key: Key {
+            skip_fields: matches.get_one("skip_fields").copied().unwrap_or(0),
+            skip_chars: matches.get_one("skip_chars").copied().unwrap_or(0),
+            check_chars: matches.get_one("check_chars").copied(),
+            ignore_case: matches.get_flag("ignore_case"),
+        },
+        delimiter: if matches.get_flag("zero_terminated") {
+            b'\0'
+        } else {
+            b'\n'
+        },
+        group: matches.get_one("group").copied(),
     }
 }
 
-/// # Errors
-///
-/// Will return `Err` if file fails to open.
-pub fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
-    }
+/// Parses a count for `-f`, `-s` or `-w`. Like GNU uniq, a count too large
+/// for a `usize` means "all of them" rather than being an error.
+fn parse_count(value: &str) -> Result<usize, ParseIntError> {
+    value.parse().or_else(|e: ParseIntError| match e.kind() {
+        IntErrorKind::PosOverflow => Ok(usize::MAX),
+        _ => Err(e),
+    })
 }
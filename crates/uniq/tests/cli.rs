@@ -45,6 +45,28 @@ const SKIP: Test = Test {
     out_count: "tests/expected/skip.txt.c.out",
 };
 
+const T7: Test = Test {
+    input: "tests/inputs/t7.txt",
+    out: "tests/expected/t7.txt.out",
+    out_count: "tests/expected/t7.txt.c.out",
+};
+
+const T8: Test = Test {
+    input: "tests/inputs/t8.txt",
+    out: "tests/expected/t8.txt.out",
+    out_count: "tests/expected/t8.txt.c.out",
+};
+
+const T9: Test = Test {
+    input: "tests/inputs/t9.txt",
+    out: "tests/expected/t9.txt.out",
+    out_count: "tests/expected/t9.txt.c.out",
+};
+
+const FIELDS: &str = "tests/inputs/fields.txt";
+const ZERO: &str = "tests/inputs/zero.bin";
+const LATIN1: &str = "tests/inputs/latin1.bin";
+
 const T1: Test = Test {
     input: "tests/inputs/t1.txt",
     out: "tests/expected/t1.txt.out",
@@ -98,12 +120,12 @@ fn gen_bad_file() -> String {
 #[test]
 fn dies_bad_file() -> Result<()> {
     let bad = gen_bad_file();
-    let expected = format!("{bad}: .* [(]os error 2[)]");
+    let expected = format!("uniq: {bad}: No such file or directory\n");
     Command::cargo_bin(PRG)?
         .arg(bad)
         .assert()
         .failure()
-        .stderr(predicate::str::is_match(expected)?);
+        .stderr(expected);
     Ok(())
 }
 
@@ -162,6 +184,26 @@ fn run_stdin_count(test: &Test) -> Result<()> {
     Ok(())
 }
 
+fn run_args(args: &[&str], expected_file: &str) -> Result<()> {
+    let expected = fs::read_to_string(expected_file)?;
+    let output = Command::cargo_bin(PRG)?.args(args).output().expect("fail");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
+    assert_eq!(stdout, expected);
+    Ok(())
+}
+
+fn run_bytes(args: &[&str], expected_file: &str) -> Result<()> {
+    let expected = fs::read(expected_file)?;
+    Command::cargo_bin(PRG)?
+        .args(args)
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
 fn run_outfile(test: &Test) -> Result<()> {
     let expected = fs::read_to_string(test.out)?;
     let outfile = NamedTempFile::new()?;
@@ -597,3 +639,715 @@ fn t6_outfile_count() -> Result<()> {
 fn t6_stdin_outfile_count() -> Result<()> {
     run_stdin_outfile_count(&T6)
 }
+
+#[test]
+fn t7() -> Result<()> {
+    run(&T7)
+}
+
+#[test]
+fn t7_count() -> Result<()> {
+    run_count(&T7)
+}
+
+#[test]
+fn t7_stdin() -> Result<()> {
+    run_stdin(&T7)
+}
+
+#[test]
+fn t7_stdin_count() -> Result<()> {
+    run_stdin_count(&T7)
+}
+
+#[test]
+fn t7_outfile() -> Result<()> {
+    run_outfile(&T7)
+}
+
+#[test]
+fn t7_outfile_count() -> Result<()> {
+    run_outfile_count(&T7)
+}
+
+#[test]
+fn t7_stdin_outfile_count() -> Result<()> {
+    run_stdin_outfile_count(&T7)
+}
+
+#[test]
+fn t7_repeated() -> Result<()> {
+    run_args(&[T7.input, "-d"], "tests/expected/t7.txt.d.out")
+}
+
+#[test]
+fn t7_all_repeated() -> Result<()> {
+    run_args(&[T7.input, "-D"], "tests/expected/t7.txt.D.out")
+}
+
+#[test]
+fn t7_unique() -> Result<()> {
+    run_args(&[T7.input, "-u"], "tests/expected/t7.txt.u.out")
+}
+
+#[test]
+fn t8() -> Result<()> {
+    run(&T8)
+}
+
+#[test]
+fn t8_count() -> Result<()> {
+    run_count(&T8)
+}
+
+#[test]
+fn t8_stdin() -> Result<()> {
+    run_stdin(&T8)
+}
+
+#[test]
+fn t8_stdin_count() -> Result<()> {
+    run_stdin_count(&T8)
+}
+
+#[test]
+fn t8_outfile() -> Result<()> {
+    run_outfile(&T8)
+}
+
+#[test]
+fn t8_outfile_count() -> Result<()> {
+    run_outfile_count(&T8)
+}
+
+#[test]
+fn t8_stdin_outfile_count() -> Result<()> {
+    run_stdin_outfile_count(&T8)
+}
+
+#[test]
+fn t8_repeated() -> Result<()> {
+    run_args(&[T8.input, "-d"], "tests/expected/t8.txt.d.out")
+}
+
+#[test]
+fn t8_all_repeated() -> Result<()> {
+    run_args(&[T8.input, "-D"], "tests/expected/t8.txt.D.out")
+}
+
+#[test]
+fn t8_unique() -> Result<()> {
+    run_args(&[T8.input, "-u"], "tests/expected/t8.txt.u.out")
+}
+
+#[test]
+fn t9() -> Result<()> {
+    run(&T9)
+}
+
+#[test]
+fn t9_count() -> Result<()> {
+    run_count(&T9)
+}
+
+#[test]
+fn t9_stdin() -> Result<()> {
+    run_stdin(&T9)
+}
+
+#[test]
+fn t9_stdin_count() -> Result<()> {
+    run_stdin_count(&T9)
+}
+
+#[test]
+fn t9_outfile() -> Result<()> {
+    run_outfile(&T9)
+}
+
+#[test]
+fn t9_outfile_count() -> Result<()> {
+    run_outfile_count(&T9)
+}
+
+#[test]
+fn t9_stdin_outfile_count() -> Result<()> {
+    run_stdin_outfile_count(&T9)
+}
+
+#[test]
+fn t9_repeated() -> Result<()> {
+    run_args(&[T9.input, "-d"], "tests/expected/t9.txt.d.out")
+}
+
+#[test]
+fn t9_all_repeated() -> Result<()> {
+    run_args(&[T9.input, "-D"], "tests/expected/t9.txt.D.out")
+}
+
+#[test]
+fn t9_unique() -> Result<()> {
+    run_args(&[T9.input, "-u"], "tests/expected/t9.txt.u.out")
+}
+
+#[test]
+fn empty_repeated() -> Result<()> {
+    run_args(&[EMPTY.input, "-d"], "tests/expected/empty.txt.d.out")
+}
+
+#[test]
+fn empty_all_repeated() -> Result<()> {
+    run_args(&[EMPTY.input, "-D"], "tests/expected/empty.txt.D.out")
+}
+
+#[test]
+fn empty_unique() -> Result<()> {
+    run_args(&[EMPTY.input, "-u"], "tests/expected/empty.txt.u.out")
+}
+
+#[test]
+fn one_repeated() -> Result<()> {
+    run_args(&[ONE.input, "-d"], "tests/expected/one.txt.d.out")
+}
+
+#[test]
+fn one_all_repeated() -> Result<()> {
+    run_args(&[ONE.input, "-D"], "tests/expected/one.txt.D.out")
+}
+
+#[test]
+fn one_unique() -> Result<()> {
+    run_args(&[ONE.input, "-u"], "tests/expected/one.txt.u.out")
+}
+
+#[test]
+fn two_repeated() -> Result<()> {
+    run_args(&[TWO.input, "-d"], "tests/expected/two.txt.d.out")
+}
+
+#[test]
+fn two_all_repeated() -> Result<()> {
+    run_args(&[TWO.input, "-D"], "tests/expected/two.txt.D.out")
+}
+
+#[test]
+fn two_unique() -> Result<()> {
+    run_args(&[TWO.input, "-u"], "tests/expected/two.txt.u.out")
+}
+
+#[test]
+fn three_repeated() -> Result<()> {
+    run_args(&[THREE.input, "-d"], "tests/expected/three.txt.d.out")
+}
+
+#[test]
+fn three_all_repeated() -> Result<()> {
+    run_args(&[THREE.input, "-D"], "tests/expected/three.txt.D.out")
+}
+
+#[test]
+fn three_unique() -> Result<()> {
+    run_args(&[THREE.input, "-u"], "tests/expected/three.txt.u.out")
+}
+
+#[test]
+fn skip_repeated() -> Result<()> {
+    run_args(&[SKIP.input, "-d"], "tests/expected/skip.txt.d.out")
+}
+
+#[test]
+fn skip_all_repeated() -> Result<()> {
+    run_args(&[SKIP.input, "-D"], "tests/expected/skip.txt.D.out")
+}
+
+#[test]
+fn skip_unique() -> Result<()> {
+    run_args(&[SKIP.input, "-u"], "tests/expected/skip.txt.u.out")
+}
+
+#[test]
+fn t1_repeated() -> Result<()> {
+    run_args(&[T1.input, "-d"], "tests/expected/t1.txt.d.out")
+}
+
+#[test]
+fn t1_all_repeated() -> Result<()> {
+    run_args(&[T1.input, "-D"], "tests/expected/t1.txt.D.out")
+}
+
+#[test]
+fn t1_unique() -> Result<()> {
+    run_args(&[T1.input, "-u"], "tests/expected/t1.txt.u.out")
+}
+
+#[test]
+fn t2_repeated() -> Result<()> {
+    run_args(&[T2.input, "-d"], "tests/expected/t2.txt.d.out")
+}
+
+#[test]
+fn t2_all_repeated() -> Result<()> {
+    run_args(&[T2.input, "-D"], "tests/expected/t2.txt.D.out")
+}
+
+#[test]
+fn t2_unique() -> Result<()> {
+    run_args(&[T2.input, "-u"], "tests/expected/t2.txt.u.out")
+}
+
+#[test]
+fn t3_repeated() -> Result<()> {
+    run_args(&[T3.input, "-d"], "tests/expected/t3.txt.d.out")
+}
+
+#[test]
+fn t3_all_repeated() -> Result<()> {
+    run_args(&[T3.input, "-D"], "tests/expected/t3.txt.D.out")
+}
+
+#[test]
+fn t3_unique() -> Result<()> {
+    run_args(&[T3.input, "-u"], "tests/expected/t3.txt.u.out")
+}
+
+#[test]
+fn t4_repeated() -> Result<()> {
+    run_args(&[T4.input, "-d"], "tests/expected/t4.txt.d.out")
+}
+
+#[test]
+fn t4_all_repeated() -> Result<()> {
+    run_args(&[T4.input, "-D"], "tests/expected/t4.txt.D.out")
+}
+
+#[test]
+fn t4_unique() -> Result<()> {
+    run_args(&[T4.input, "-u"], "tests/expected/t4.txt.u.out")
+}
+
+#[test]
+fn t5_repeated() -> Result<()> {
+    run_args(&[T5.input, "-d"], "tests/expected/t5.txt.d.out")
+}
+
+#[test]
+fn t5_all_repeated() -> Result<()> {
+    run_args(&[T5.input, "-D"], "tests/expected/t5.txt.D.out")
+}
+
+#[test]
+fn t5_unique() -> Result<()> {
+    run_args(&[T5.input, "-u"], "tests/expected/t5.txt.u.out")
+}
+
+#[test]
+fn t6_repeated() -> Result<()> {
+    run_args(&[T6.input, "-d"], "tests/expected/t6.txt.d.out")
+}
+
+#[test]
+fn t6_all_repeated() -> Result<()> {
+    run_args(&[T6.input, "-D"], "tests/expected/t6.txt.D.out")
+}
+
+#[test]
+fn t6_unique() -> Result<()> {
+    run_args(&[T6.input, "-u"], "tests/expected/t6.txt.u.out")
+}
+
+#[test]
+fn three_repeated_count() -> Result<()> {
+    run_args(
+        &[THREE.input, "--repeated", "--count"],
+        "tests/expected/three.txt.dc.out",
+    )
+}
+
+#[test]
+fn three_unique_count() -> Result<()> {
+    run_args(
+        &[THREE.input, "--unique", "-c"],
+        "tests/expected/three.txt.uc.out",
+    )
+}
+
+#[test]
+fn three_repeated_unique() -> Result<()> {
+    run_args(
+        &[THREE.input, "-d", "-u"],
+        "tests/expected/three.txt.du.out",
+    )
+}
+
+#[test]
+fn three_all_repeated_unique() -> Result<()> {
+    run_args(
+        &[THREE.input, "-D", "-u"],
+        "tests/expected/three.txt.Du.out",
+    )
+}
+
+#[test]
+fn three_all_repeated_none() -> Result<()> {
+    run_args(
+        &[THREE.input, "--all-repeated=none"],
+        "tests/expected/three.txt.D.out",
+    )
+}
+
+#[test]
+fn three_all_repeated_prepend() -> Result<()> {
+    run_args(
+        &[THREE.input, "--all-repeated=prepend"],
+        "tests/expected/three.txt.D-prepend.out",
+    )
+}
+
+#[test]
+fn three_all_repeated_separate() -> Result<()> {
+    run_args(
+        &["--all-repeated=separate", THREE.input],
+        "tests/expected/three.txt.D-separate.out",
+    )
+}
+
+#[test]
+fn three_all_repeated_outfile() -> Result<()> {
+    let outfile = NamedTempFile::new()?;
+    let outpath = &outfile.path().to_str().unwrap();
+
+    Command::cargo_bin(PRG)?
+        .args(["-D", THREE.input, outpath])
+        .assert()
+        .success()
+        .stdout("");
+
+    let expected = fs::read_to_string("tests/expected/three.txt.D.out")?;
+    let contents = fs::read_to_string(outpath)?;
+    assert_eq!(&expected, &contents);
+
+    Ok(())
+}
+
+#[test]
+fn dies_all_repeated_count() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["-D", "-c", THREE.input])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    Ok(())
+}
+
+#[test]
+fn dies_bad_delimit_method() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["--all-repeated=append", THREE.input])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'append' for '--all-repeated[=<METHOD>]'",
+        ));
+    Ok(())
+}
+
+#[test]
+fn dies_short_all_repeated_with_method() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["-d", "-D=separate", THREE.input])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '-='"));
+    Ok(())
+}
+
+#[test]
+fn fields() -> Result<()> {
+    run_args(&[FIELDS], "tests/expected/fields.txt.out")
+}
+
+#[test]
+fn fields_count() -> Result<()> {
+    run_args(&[FIELDS, "-c"], "tests/expected/fields.txt.c.out")
+}
+
+#[test]
+fn fields_skip_fields() -> Result<()> {
+    run_args(&[FIELDS, "-f", "1"], "tests/expected/fields.txt.f1.out")
+}
+
+#[test]
+fn fields_skip_chars() -> Result<()> {
+    run_args(
+        &[FIELDS, "--skip-chars", "2"],
+        "tests/expected/fields.txt.s2.out",
+    )
+}
+
+#[test]
+fn fields_check_chars() -> Result<()> {
+    run_args(
+        &[FIELDS, "--check-chars=3"],
+        "tests/expected/fields.txt.w3.out",
+    )
+}
+
+#[test]
+fn fields_ignore_case() -> Result<()> {
+    run_args(
+        &[FIELDS, "--ignore-case"],
+        "tests/expected/fields.txt.i.out",
+    )
+}
+
+#[test]
+fn fields_skip_fields_ignore_case() -> Result<()> {
+    run_args(&[FIELDS, "-f1", "-i"], "tests/expected/fields.txt.f1i.out")
+}
+
+#[test]
+fn fields_skip_fields_check_chars() -> Result<()> {
+    run_args(
+        &[FIELDS, "-f", "1", "-w", "6"],
+        "tests/expected/fields.txt.f1w6.out",
+    )
+}
+
+#[test]
+fn fields_skip_fields_skip_chars_check_chars() -> Result<()> {
+    run_args(
+        &[FIELDS, "-f", "1", "-s", "1", "-w", "5"],
+        "tests/expected/fields.txt.f1s1w5.out",
+    )
+}
+
+#[test]
+fn fields_count_skip_fields_ignore_case() -> Result<()> {
+    run_args(
+        &[FIELDS, "-c", "-f", "1", "-i"],
+        "tests/expected/fields.txt.cf1i.out",
+    )
+}
+
+#[test]
+fn fields_all_repeated_key() -> Result<()> {
+    run_args(
+        &[FIELDS, "-D", "-f", "1", "-w", "6", "-i"],
+        "tests/expected/fields.txt.Df1w6i.out",
+    )
+}
+
+#[test]
+fn skip_skip_chars() -> Result<()> {
+    run_args(&[SKIP.input, "-s", "1"], "tests/expected/skip.txt.s1.out")
+}
+
+#[test]
+fn skip_check_chars() -> Result<()> {
+    run_args(&[SKIP.input, "-w", "1"], "tests/expected/skip.txt.w1.out")
+}
+
+#[test]
+fn skip_count_skip_fields() -> Result<()> {
+    run_args(
+        &[SKIP.input, "-c", "-f", "1"],
+        "tests/expected/skip.txt.cf1.out",
+    )
+}
+
+#[test]
+fn dies_bad_skip_fields() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["-f", "x", FIELDS])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'x' for '--skip-fields <N>'",
+        ));
+    Ok(())
+}
+
+#[test]
+fn huge_skip_counts() -> Result<()> {
+    for args in [
+        &["-f", "18446744073709551615"][..],
+        &["-f", "1", "-s", "18446744073709551615"],
+        &["-s", "99999999999999999999999"],
+    ] {
+        Command::cargo_bin(PRG)?
+            .args(args)
+            .write_stdin("ab\nac\n")
+            .timeout(std::time::Duration::from_secs(10))
+            .assert()
+            .success()
+            .stdout("ab\n");
+    }
+    Ok(())
+}
+
+#[test]
+fn zero_newline_delimited() -> Result<()> {
+    run_bytes(&[ZERO], "tests/expected/zero.bin.out")
+}
+
+#[test]
+fn zero() -> Result<()> {
+    run_bytes(&["-z", ZERO], "tests/expected/zero.bin.z.out")
+}
+
+#[test]
+fn zero_count() -> Result<()> {
+    run_bytes(
+        &["--zero-terminated", "-c", ZERO],
+        "tests/expected/zero.bin.zc.out",
+    )
+}
+
+#[test]
+fn zero_unique() -> Result<()> {
+    run_bytes(&["-zu", ZERO], "tests/expected/zero.bin.zu.out")
+}
+
+#[test]
+fn zero_all_repeated_separate() -> Result<()> {
+    run_bytes(
+        &["-z", "--all-repeated=separate", ZERO],
+        "tests/expected/zero.bin.zD-separate.out",
+    )
+}
+
+#[test]
+fn zero_stdin() -> Result<()> {
+    let input = fs::read(ZERO)?;
+    let expected = fs::read("tests/expected/zero.bin.z.out")?;
+    Command::cargo_bin(PRG)?
+        .arg("-z")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
+#[test]
+fn latin1() -> Result<()> {
+    run_bytes(&[LATIN1], "tests/expected/latin1.bin.out")
+}
+
+#[test]
+fn latin1_count() -> Result<()> {
+    run_bytes(&[LATIN1, "-c"], "tests/expected/latin1.bin.c.out")
+}
+
+#[test]
+fn latin1_ignore_case() -> Result<()> {
+    run_bytes(&[LATIN1, "-i"], "tests/expected/latin1.bin.i.out")
+}
+
+#[test]
+fn latin1_outfile() -> Result<()> {
+    let outfile = NamedTempFile::new()?;
+    let outpath = &outfile.path().to_str().unwrap();
+
+    Command::cargo_bin(PRG)?
+        .args([LATIN1, outpath])
+        .assert()
+        .success()
+        .stdout("");
+
+    let expected = fs::read("tests/expected/latin1.bin.out")?;
+    let contents = fs::read(outpath)?;
+    assert_eq!(expected, contents);
+
+    Ok(())
+}
+
+#[test]
+fn three_group() -> Result<()> {
+    run_args(
+        &[THREE.input, "--group"],
+        "tests/expected/three.txt.group.out",
+    )
+}
+
+#[test]
+fn three_group_separate() -> Result<()> {
+    run_args(
+        &[THREE.input, "--group=separate"],
+        "tests/expected/three.txt.group-separate.out",
+    )
+}
+
+#[test]
+fn three_group_prepend() -> Result<()> {
+    run_args(
+        &[THREE.input, "--group=prepend"],
+        "tests/expected/three.txt.group-prepend.out",
+    )
+}
+
+#[test]
+fn three_group_append() -> Result<()> {
+    run_args(
+        &[THREE.input, "--group=append"],
+        "tests/expected/three.txt.group-append.out",
+    )
+}
+
+#[test]
+fn three_group_both() -> Result<()> {
+    run_args(
+        &["--group=both", THREE.input],
+        "tests/expected/three.txt.group-both.out",
+    )
+}
+
+#[test]
+fn empty_group_both() -> Result<()> {
+    run_args(
+        &[EMPTY.input, "--group=both"],
+        "tests/expected/empty.txt.group-both.out",
+    )
+}
+
+#[test]
+fn fields_group_key() -> Result<()> {
+    run_args(
+        &[FIELDS, "--group", "-f", "1", "-i"],
+        "tests/expected/fields.txt.group-f1i.out",
+    )
+}
+
+#[test]
+fn zero_group_skip_fields() -> Result<()> {
+    run_bytes(
+        &[ZERO, "-z", "-f", "1", "--group=both"],
+        "tests/expected/zero.bin.zf1-group-both.out",
+    )
+}
+
+#[test]
+fn dies_group_with_output_mode() -> Result<()> {
+    for flag in ["-c", "-d", "-D", "-u", "--all-repeated=separate"] {
+        Command::cargo_bin(PRG)?
+            .args(["--group", flag, THREE.input])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains(
+                "the argument '--group[=<METHOD>]' cannot be used with",
+            ));
+    }
+    Ok(())
+}
+
+#[test]
+fn dies_bad_group_method() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["--group=none", THREE.input])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid value 'none' for '--group[=<METHOD>]'",
+        ));
+    Ok(())
+}
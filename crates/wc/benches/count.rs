@@ -0,0 +1,142 @@
+//! Compares `wc::count` with the line-at-a-time implementation it replaced.
+
+use std::io::{self, BufRead, BufReader, Cursor};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use unicode_width::UnicodeWidthChar;
+use wc::{Config, Counts, Total};
+
+const INPUT_SIZE: usize = 8 * 1024 * 1024;
+const BUFFER_SIZE: usize = 256 * 1024;
+
+/// Builds a log-like input of mostly ASCII text with some multibyte and
+/// invalid sequences.
+fn input() -> Vec<u8> {
+    let lines: [&[u8]; 4] = [
+        b"2024-05-01T12:00:00Z INFO request handled in 12ms path=/api/v1/items\n",
+        b"2024-05-01T12:00:01Z WARN \xe3\x82\xad\xe3\x83\xa3\xe3\x83\x83\xe3\x82\xb7\xe3\x83\xa5 miss\tkey=caf\xc3\xa9\n",
+        b"2024-05-01T12:00:02Z ERROR upstream returned \xff\xfe garbage\n",
+        b"\n",
+    ];
+    lines
+        .iter()
+        .copied()
+        .cycle()
+        .flatten()
+        .copied()
+        .take(INPUT_SIZE)
+        .collect()
+}
+
+/// Selects the counts named by `flags`, as wc's short options do.
+fn config(flags: &str) -> Config {
+    Config {
+        files: Vec::new(),
+        files0_from: None,
+        lines: flags.contains('l'),
+        words: flags.contains('w'),
+        chars: flags.contains('m'),
+        bytes: flags.contains('c'),
+        max_line_length: flags.contains('L'),
+        total: Total::Auto,
+    }
+}
+
+/// A copy of `wc::count` as it was before reading in large buffers: one
+/// allocation-backed line at a time, decoding every line whatever the
+/// selected counts.
+fn line_at_a_time(mut file: impl BufRead, counts: &mut Counts) -> io::Result<()> {
+    let mut line = Vec::new();
+    let mut in_word = false;
+    let mut line_pos = 0;
+
+    loop {
+        line.clear();
+        if file.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        counts.bytes += line.len();
+        // A line never splits a multibyte sequence, as '\n' cannot occur
+        // inside one.
+        for chunk in line.utf8_chunks() {
+            for c in chunk.valid().chars() {
+                counts.chars += 1;
+                match c {
+                    '\n' | '\r' | '\x0c' => {
+                        counts.lines += usize::from(c == '\n');
+                        counts.max_line_length = counts.max_line_length.max(line_pos);
+                        line_pos = 0;
+                        in_word = false;
+                    }
+                    '\t' => {
+                        line_pos += 8 - line_pos % 8;
+                        in_word = false;
+                    }
+                    '\x0b' => in_word = false,
+                    _ if c.is_control() => {}
+                    _ => {
+                        line_pos += char_width(c);
+                        if is_space(c) {
+                            in_word = false;
+                        } else if !in_word {
+                            counts.words += 1;
+                            in_word = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    counts.max_line_length = counts.max_line_length.max(line_pos);
+    Ok(())
+}
+
+/// Returns the number of columns `c` takes on a terminal. Unlike glibc,
+/// `unicode-width` gives the soft hyphen no width.
+fn char_width(c: char) -> usize {
+    if c == '\u{ad}' {
+        1
+    } else {
+        c.width().unwrap_or(0)
+    }
+}
+
+/// Returns whether `c` separates words: white space, including the no-break
+/// spaces.
+const fn is_space(c: char) -> bool {
+    c.is_whitespace() || c == '\u{2060}'
+}
+
+fn bench_count(c: &mut Criterion) {
+    let input = input();
+    let mut group = c.benchmark_group("count");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    group.bench_function("line_at_a_time", |b| {
+        b.iter(|| {
+            let mut counts = Counts::default();
+            line_at_a_time(BufReader::new(Cursor::new(&input)), &mut counts).ok();
+            counts
+        });
+    });
+    for (name, config) in [
+        ("default", config("lwc")),
+        ("lines", config("l")),
+        ("bytes", config("c")),
+        ("chars", config("m")),
+        ("all", config("lwmcL")),
+    ] {
+        group.bench_with_input(BenchmarkId::new("chunked", name), &config, |b, config| {
+            b.iter(|| {
+                let mut counts = Counts::default();
+                let file = BufReader::with_capacity(BUFFER_SIZE, Cursor::new(&input));
+                wc::count(file, config, &mut counts).ok();
+                counts
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_count);
+criterion_main!(benches);
@@ -1,11 +1,18 @@
 #!/usr/bin/env bash
 
+# -m and -L depend on the locale; the expected outputs assume UTF-8.
+export LC_ALL=C.UTF-8
+
 ROOT="tests/inputs"
 FILES="$ROOT/empty.txt $ROOT/fox.txt $ROOT/atlamal.txt"
 OUT_DIR="tests/expected"
 
 [[ ! -d "$OUT_DIR" ]] && mkdir -p "$OUT_DIR"
 
+# Invalid UTF-8, truncated sequences, control characters, Unicode spaces and a
+# final line without a newline.
+printf 'caf\xc3\xa9 na\xefve\n\xff\xfe \x01 a\x01b\n\xe2\x82 x\xc2\xa0y\xe3\x80\x80z\n\xc2\x85\tend\xe2\x81\xa0word \xc3' > "$ROOT/mixed.bin"
+
 for FILE in $FILES; do
     BASENAME=$(basename "$FILE")
     wc      $FILE > ${OUT_DIR}/${BASENAME}.out
@@ -19,9 +26,34 @@ for FILE in $FILES; do
     wc -wl  $FILE > ${OUT_DIR}/${BASENAME}.wl.out
     wc -cl  $FILE > ${OUT_DIR}/${BASENAME}.cl.out
     wc -ml  $FILE > ${OUT_DIR}/${BASENAME}.ml.out
+    wc -L   $FILE > ${OUT_DIR}/${BASENAME}.L.out
+    wc -cm  $FILE > ${OUT_DIR}/${BASENAME}.cm.out
 done
 
+wc -L  "$ROOT/wide.txt" > "$OUT_DIR/wide.txt.L.out"
+wc -lL "$ROOT/wide.txt" > "$OUT_DIR/wide.txt.lL.out"
+
+wc -lwmcL "$ROOT/mixed.bin" > "$OUT_DIR/mixed.bin.lwmcL.out"
+wc "$ROOT/mixed.bin" > "$OUT_DIR/mixed.bin.out"
+wc -m "$ROOT/mixed.bin" > "$OUT_DIR/mixed.bin.m.out"
+wc -w "$ROOT/mixed.bin" > "$OUT_DIR/mixed.bin.w.out"
+wc "$ROOT" > "$OUT_DIR/dir.out" 2> "$OUT_DIR/dir.err"
+
+printf '%s\0' $FILES > "$ROOT/files0.txt"
+printf '%s\0' "$ROOT/fox.txt" "" - > "$ROOT/files0-bad.txt"
+wc --files0-from="$ROOT/files0.txt" > "$OUT_DIR/files0.out"
+wc --files0-from=- < "$ROOT/files0.txt" > "$OUT_DIR/files0.stdin.out"
+cat "$ROOT/files0.txt" | wc --files0-from=- > "$OUT_DIR/files0.pipe.out"
+wc --files0-from=- < "$ROOT/files0-bad.txt" > "$OUT_DIR/files0-bad.out" 2> "$OUT_DIR/files0-bad.err"
+
+# A file name that is not valid UTF-8, as find -print0 may well produce.
+LATIN1="$ROOT/$(printf 'caf\xe9.txt')"
+cp "$ROOT/fox.txt" "$LATIN1"
+printf '%s\0' "$ROOT/empty.txt" "$LATIN1" > "$ROOT/files0-latin1.txt"
+wc --files0-from="$ROOT/files0-latin1.txt" > "$OUT_DIR/files0-latin1.out"
+
 wc < "$ROOT/atlamal.txt" > "$OUT_DIR/atlamal.txt.stdin.out"
+cat "$ROOT/atlamal.txt" | wc > "$OUT_DIR/atlamal.txt.pipe.out"
 
 wc      $FILES > $OUT_DIR/all.out
 wc -l   $FILES > $OUT_DIR/all.l.out
@@ -34,3 +66,8 @@ wc -wm  $FILES > $OUT_DIR/all.wm.out
 wc -wl  $FILES > $OUT_DIR/all.wl.out
 wc -cl  $FILES > $OUT_DIR/all.cl.out
 wc -ml  $FILES > $OUT_DIR/all.ml.out
+wc -L   $FILES > $OUT_DIR/all.L.out
+wc -lL  $FILES > $OUT_DIR/all.lL.out
+wc -cm  $FILES > $OUT_DIR/all.cm.out
+wc -lwmcL $FILES > $OUT_DIR/all.lwmcL.out
+wc -lwmcL - < "$ROOT/fox.txt" > "$OUT_DIR/fox.txt.stdin.lwmcL.out"
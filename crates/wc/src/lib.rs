@@ -1,143 +1,539 @@
-use std::io::{self, BufRead, BufReader};
-use std::{error::Error, fs::File};
+use std::ffi::{OsStr, OsString};
+use std::fs::{self, File};
+use std::io::{self, BufRead, Write};
+use std::ops::AddAssign;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::str;
 
-use anyhow::Result;
-use clap::{Arg, ArgAction, Command};
+use anyhow::{anyhow, Result};
+use clap::{value_parser, Arg, ArgAction, Command, ValueEnum};
 use indoc::indoc;
+use roarutils_core::{format_error, open_with_capacity, Reporter};
+use unicode_width::UnicodeWidthChar;
 
-#[allow(dead_code, clippy::struct_excessive_bools)]
+/// The size of the buffers input is read and counted in.
+const BUFFER_SIZE: usize = 256 * 1024;
+
+#[allow(clippy::struct_excessive_bools)]
 #[derive(Debug)]
 pub struct Config {
-    files: Vec<String>,
-    lines: bool,
-    words: bool,
-    bytes: bool,
-    chars: bool,
+    /// The files to count; standard input if empty.
+    pub files: Vec<String>,
+    /// A file of NUL-terminated names to count instead of `files`.
+    pub files0_from: Option<String>,
+    pub lines: bool,
+    pub words: bool,
+    pub chars: bool,
+    pub bytes: bool,
+    pub max_line_length: bool,
+    pub total: Total,
+}
+
+/// When `--total` prints a line with the total counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Total {
+    /// When more than one file is counted.
+    Auto,
+    /// Always, even for a single file.
+    Always,
+    /// Always, and without the counts for each file.
+    Only,
+    /// Never.
+    Never,
+}
+
+/// An input to count, from the command line or a `--files0-from` list.
+enum Operand {
+    /// Standard input when no FILE is given, whose name is not printed.
+    Unnamed,
+    /// A file name, kept as raw bytes since it need not be valid UTF-8.
+    Name(OsString),
+    /// An unusable `--files0-from` entry, with the error to report for it.
+    Invalid(String),
+}
+
+impl Config {
+    /// Returns the number of columns printed for each file.
+    fn num_columns(&self) -> usize {
+        [
+            self.lines,
+            self.words,
+            self.chars,
+            self.bytes,
+            self.max_line_length,
+        ]
+        .iter()
+        .filter(|&&v| v)
+        .count()
+    }
+
+    /// Returns whether the input has to be decoded, rather than only scanned
+    /// for newlines.
+    const fn decodes(&self) -> bool {
+        self.words || self.chars || self.max_line_length
+    }
+}
+
+/// The counts for one input, or the running total of several.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counts {
+    pub lines: usize,
+    pub words: usize,
+    pub chars: usize,
+    pub bytes: usize,
+    pub max_line_length: usize,
+}
+
+impl Counts {
+    /// Returns the selected counts in the order GNU wc prints them: newline,
+    /// word, character, byte, maximum line length.
+    fn columns(&self, config: &Config) -> Vec<usize> {
+        [
+            (config.lines, self.lines),
+            (config.words, self.words),
+            (config.chars, self.chars),
+            (config.bytes, self.bytes),
+            (config.max_line_length, self.max_line_length),
+        ]
+        .into_iter()
+        .filter_map(|(selected, n)| selected.then_some(n))
+        .collect()
+    }
+}
+
+impl AddAssign for Counts {
+    /// Sums the counts, except the maximum line length, which becomes the
+    /// longer of the two.
+    fn add_assign(&mut self, other: Self) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+        self.max_line_length = self.max_line_length.max(other.max_line_length);
+    }
 }
 
 /// # Errors
 ///
-/// Will return `Err` if a file does not exist.
-pub fn run(config: &Config) -> Result<()> {
-    let mut results = Vec::with_capacity(config.files.len());
-    let mut tab_size = 0;
-    for filename in &config.files {
-        match open(filename) {
-            Err(_) => eprintln!("wc: {filename}: No such file or directory"),
-            Ok(file) => {
-                if let Ok(counts) = count(file, filename, config) {
-                    tab_size = tab_size.max(counts.2);
-                    results.push(counts);
+/// Will return `Err` if the `--files0-from` list cannot be read. Inputs that
+/// cannot be opened or read are reported on standard error and make the
+/// returned exit status 1.
+pub fn run(config: &Config) -> Result<i32> {
+    let (operands, width) = match &config.files0_from {
+        None => {
+            // With no FILE operands standard input is read, and its name is
+            // omitted.
+            let operands = if config.files.is_empty() {
+                vec![Operand::Unnamed]
+            } else {
+                config
+                    .files
+                    .iter()
+                    .map(|filename| Operand::Name(filename.into()))
+                    .collect()
+            };
+            let width = number_width(config, &operands);
+            (operands, width)
+        }
+        Some(list) => {
+            let operands = read_files0(list)?;
+            // Like GNU wc, only size the columns up front when the list is a
+            // regular file; a list streamed through a pipe gets no padding.
+            let width = if is_regular(Path::new(list)) {
+                number_width(config, &operands)
+            } else {
+                1
+            };
+            (operands, width)
+        }
+    };
+
+    let mut reporter = Reporter::new("wc");
+    let mut total = Counts::default();
+    for operand in &operands {
+        let (filename, name) = match operand {
+            Operand::Unnamed => (Path::new("-"), None),
+            Operand::Name(filename) => (Path::new(filename), Some(filename.as_os_str())),
+            Operand::Invalid(message) => {
+                reporter.message(message);
+                continue;
+            }
+        };
+        // Counting only bytes of a regular file needs no reading; an empty
+        // size may just be unknown, as for files in /proc.
+        if config.bytes && !config.lines && !config.decodes() && filename.as_os_str() != "-" {
+            if let Some(bytes) = file_size(filename) {
+                let counts = Counts {
+                    bytes,
+                    ..Counts::default()
+                };
+                if config.total != Total::Only {
+                    print_counts(&counts, config, width, name)?;
                 }
+                total += counts;
+                continue;
+            }
+        }
+        match open_with_capacity(filename, BUFFER_SIZE) {
+            // GNU wc opens a directory and only fails when reading it, so its
+            // counts are still printed.
+            Err(e) if e.kind() != io::ErrorKind::IsADirectory => {
+                reporter.error(filename.display(), &e);
+            }
+            file => {
+                // Like GNU wc, whatever was counted before a read error is
+                // still printed.
+                let mut counts = Counts::default();
+                if let Err(e) = file.and_then(|file| count(file, config, &mut counts)) {
+                    reporter.error(filename.display(), &e);
+                }
+                if config.total != Total::Only {
+                    print_counts(&counts, config, width, name)?;
+                }
+                total += counts;
             }
         }
     }
-    for result in &results {
-        if config.files.len() == 1 && result.1.len() == 1 {
-            print!("{}", result.1[0]);
-        } else {
-            let formatted_figures: Vec<String> =
-                result.1.iter().map(|f| format!("{f:>tab_size$}")).collect();
-            print!("{}", formatted_figures.join(" "));
+
+    match config.total {
+        Total::Auto if operands.len() > 1 => {
+            print_counts(&total, config, width, Some(OsStr::new("total")))?;
         }
-        if result.0 == "-" {
-            println!();
-        } else {
-            println!(" {}", result.0);
+        Total::Always => print_counts(&total, config, width, Some(OsStr::new("total")))?,
+        Total::Only => print_counts(&total, config, width, None)?,
+        Total::Auto | Total::Never => {}
+    }
+
+    Ok(reporter.status())
+}
+
+/// Reads the NUL-terminated file names in `list`, or standard input if it is
+/// `-`. Empty names, and `-` when the names come from standard input, are
+/// returned as invalid so they are reported in turn.
+fn read_files0(list: &str) -> Result<Vec<Operand>> {
+    let mut file = open_with_capacity(list, BUFFER_SIZE).map_err(|e| {
+        anyhow!(format_error(
+            "wc",
+            format!("cannot open '{list}' for reading"),
+            &e
+        ))
+    })?;
+    let mut operands = Vec::new();
+    let mut name = Vec::new();
+    loop {
+        name.clear();
+        let read = file
+            .read_until(b'\0', &mut name)
+            .map_err(|e| anyhow!(format_error("wc", format!("{list}: read error"), &e)))?;
+        if read == 0 {
+            break;
         }
+        if name.last() == Some(&b'\0') {
+            name.pop();
+        }
+        let operand = match name.as_slice() {
+            [] => Operand::Invalid(format!(
+                "{list}:{}: invalid zero-length file name",
+                operands.len() + 1
+            )),
+            b"-" if list == "-" => Operand::Invalid(
+                "when reading file names from stdin, no file name of '-' allowed".to_owned(),
+            ),
+            name => Operand::Name(OsStr::from_bytes(name).to_owned()),
+        };
+        operands.push(operand);
     }
+    Ok(operands)
+}
 
-    if config.files.len() > 1 {
-        let totals = results
-            .iter()
-            .map(|r| r.1.clone())
-            .reduce(|a, b| a.iter().zip(b.iter()).map(|(x, y)| x + y).collect())
-            .unwrap_or_else(Vec::new);
-        let formatted_totals: Vec<String> =
-            totals.iter().map(|f| format!("{f:>tab_size$}")).collect();
-        print!("{}", formatted_totals.join(" "));
-        println!(" total");
+/// Prints a line of counts, followed by `name` as raw bytes, as GNU wc does.
+fn print_counts(
+    counts: &Counts,
+    config: &Config,
+    width: usize,
+    name: Option<&OsStr>,
+) -> io::Result<()> {
+    let formatted: Vec<String> = counts
+        .columns(config)
+        .iter()
+        .map(|n| format!("{n:>width$}"))
+        .collect();
+    let mut line = formatted.join(" ").into_bytes();
+    if let Some(name) = name {
+        line.push(b' ');
+        line.extend_from_slice(name.as_bytes());
     }
+    line.push(b'\n');
+    io::stdout().write_all(&line)
+}
 
-    Ok(())
+/// Computes the column width the way GNU wc does, before any input is read:
+/// enough digits for the combined size of the regular files, at least 7 when
+/// an input such as a pipe has no known size, and no padding at all for a
+/// single count of a single file.
+fn number_width(config: &Config, operands: &[Operand]) -> usize {
+    if operands.len() == 1 && config.num_columns() == 1 {
+        return 1;
+    }
+    let mut min_width = 1;
+    let mut regular_total = 0;
+    for operand in operands {
+        let filename = match operand {
+            Operand::Unnamed => Path::new("-"),
+            Operand::Name(filename) => Path::new(filename),
+            Operand::Invalid(_) => continue,
+        };
+        match metadata(filename) {
+            Ok(metadata) if metadata.is_file() => regular_total += metadata.len(),
+            Ok(_) => min_width = 7,
+            Err(_) => {}
+        }
+    }
+    count_digits(regular_total).max(min_width)
+}
+
+fn is_regular(filename: &Path) -> bool {
+    metadata(filename).is_ok_and(|metadata| metadata.is_file())
+}
+
+/// Returns the size of `filename` if it is a non-empty regular file that can
+/// be opened.
+fn file_size(filename: &Path) -> Option<usize> {
+    let metadata = File::open(filename).and_then(|file| file.metadata()).ok()?;
+    if metadata.is_file() && metadata.len() > 0 {
+        usize::try_from(metadata.len()).ok()
+    } else {
+        None
+    }
 }
 
+/// Returns the metadata of `filename`, or of standard input if it is `-`.
+fn metadata(filename: &Path) -> io::Result<fs::Metadata> {
+    fs::metadata(if filename.as_os_str() == "-" {
+        Path::new("/dev/stdin")
+    } else {
+        filename
+    })
+}
+
+/// Adds the counts for `file` to `counts`.
+///
+/// Input is counted as raw bytes a buffer at a time, and decoded as UTF-8 only
+/// when characters, words or line widths are selected in `config`. As in GNU
+/// wc, bytes that are not part of a valid UTF-8 sequence are not characters
+/// and neither start nor end a word, and characters that are not printable do
+/// not start a word either.
+///
 /// # Errors
 ///
-/// Will return `Err` if `read_line()` fails.
-pub fn count<'a>(
-    mut file: impl BufRead,
-    filename: &'a str,
-    config: &'a Config,
-) -> Result<(&'a str, Vec<usize>, usize)> {
-    let mut num_lines = 0;
-    let mut num_words = 0;
-    let mut num_bytes = 0;
-    let mut num_chars = 0;
-    let mut line = String::new();
-
+/// Will return `Err` if reading `file` fails, in which case `counts` holds
+/// what was read before the error.
+pub fn count(mut file: impl BufRead, config: &Config, counts: &mut Counts) -> io::Result<()> {
+    let mut counter = Counter {
+        per_char: config.words || config.max_line_length,
+        ..Counter::default()
+    };
     loop {
-        let line_bytes = file.read_line(&mut line)?;
+        let buf = match file.fill_buf() {
+            Ok([]) => break,
+            Ok(buf) => buf,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                *counts += counter.finish();
+                return Err(e);
+            }
+        };
+        if config.decodes() {
+            counter.update(buf);
+        } else {
+            counter.counts.bytes += buf.len();
+            if config.lines {
+                counter.counts.lines += memchr::memchr_iter(b'\n', buf).count();
+            }
+        }
+        let len = buf.len();
+        file.consume(len);
+    }
+    *counts += counter.finish();
+    Ok(())
+}
 
-        if line_bytes == 0 {
-            break;
+/// Decodes input a buffer at a time, carrying the state of the current word
+/// and line, and any multibyte sequence split between buffers, over to the
+/// next buffer.
+#[derive(Debug, Default)]
+struct Counter {
+    counts: Counts,
+    /// Whether words or line widths are needed, which means looking at each
+    /// character rather than just counting them.
+    per_char: bool,
+    in_word: bool,
+    line_pos: usize,
+    partial: [u8; 4],
+    partial_len: usize,
+}
+
+impl Counter {
+    fn update(&mut self, mut buf: &[u8]) {
+        self.counts.bytes += buf.len();
+        if self.partial_len > 0 {
+            buf = self.complete_partial(buf);
+        }
+        if !self.per_char {
+            self.counts.lines += memchr::memchr_iter(b'\n', buf).count();
+        }
+        let mut chunks = buf.utf8_chunks().peekable();
+        while let Some(chunk) = chunks.next() {
+            if self.per_char {
+                self.add_str(chunk.valid());
+            } else {
+                self.counts.chars += chunk.valid().chars().count();
+            }
+            let invalid = chunk.invalid();
+            if chunks.peek().is_none() && is_incomplete(invalid) {
+                self.partial[..invalid.len()].copy_from_slice(invalid);
+                self.partial_len = invalid.len();
+            }
         }
-        num_bytes += line_bytes;
-        num_lines += 1;
-        num_words += line.split_whitespace().count();
-        num_chars += line.chars().count();
-        line.clear();
     }
-    let mut counts = Vec::with_capacity(4);
-    if config.lines {
-        counts.push(num_lines);
+
+    /// Decodes the character begun by the bytes left over from the previous
+    /// buffer, and returns the rest of `buf`.
+    fn complete_partial<'a>(&mut self, buf: &'a [u8]) -> &'a [u8] {
+        let len = self.partial_len;
+        let taken = (self.partial.len() - len).min(buf.len());
+        let mut bytes = self.partial;
+        bytes[len..len + taken].copy_from_slice(&buf[..taken]);
+        let bytes = &bytes[..len + taken];
+        self.partial_len = 0;
+
+        let first = bytes.utf8_chunks().next();
+        match first.and_then(|chunk| chunk.valid().chars().next()) {
+            Some(c) => {
+                self.add_char(c);
+                &buf[c.len_utf8() - len..]
+            }
+            // The buffer was too short to complete the sequence.
+            None if taken == buf.len() && is_incomplete(bytes) => {
+                self.partial[..bytes.len()].copy_from_slice(bytes);
+                self.partial_len = bytes.len();
+                &[]
+            }
+            // The left over bytes were invalid after all; the following ones
+            // cannot continue them, so they start afresh.
+            None => buf,
+        }
     }
-    if config.words {
-        counts.push(num_words);
+
+    /// Adds the characters of `s`, taking the common ASCII ones a byte at a
+    /// time rather than decoding them.
+    fn add_str(&mut self, s: &str) {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+            if b.is_ascii_graphic() {
+                self.counts.chars += 1;
+                self.line_pos += 1;
+                if !self.in_word {
+                    self.counts.words += 1;
+                    self.in_word = true;
+                }
+                i += 1;
+            } else if b.is_ascii() {
+                self.add_char(char::from(b));
+                i += 1;
+            } else {
+                let Some(c) = s[i..].chars().next() else {
+                    break;
+                };
+                self.add_char(c);
+                i += c.len_utf8();
+            }
+        }
     }
-    if config.bytes {
-        counts.push(num_bytes);
+
+    fn add_char(&mut self, c: char) {
+        let counts = &mut self.counts;
+        counts.chars += 1;
+        match c {
+            '\n' | '\r' | '\x0c' => {
+                counts.lines += usize::from(c == '\n');
+                counts.max_line_length = counts.max_line_length.max(self.line_pos);
+                self.line_pos = 0;
+                self.in_word = false;
+            }
+            '\t' => {
+                self.line_pos += 8 - self.line_pos % 8;
+                self.in_word = false;
+            }
+            ' ' => {
+                self.line_pos += 1;
+                self.in_word = false;
+            }
+            '\x0b' => self.in_word = false,
+            _ if c.is_ascii_graphic() => {
+                self.line_pos += 1;
+                if !self.in_word {
+                    counts.words += 1;
+                    self.in_word = true;
+                }
+            }
+            _ if c.is_control() => {}
+            _ => {
+                self.line_pos += char_width(c);
+                if is_space(c) {
+                    self.in_word = false;
+                } else if !self.in_word {
+                    counts.words += 1;
+                    self.in_word = true;
+                }
+            }
+        }
     }
-    if config.chars {
-        counts.push(num_chars);
+
+    /// Returns the counts, treating any incomplete sequence at the end of the
+    /// input as invalid.
+    fn finish(self) -> Counts {
+        let mut counts = self.counts;
+        counts.max_line_length = counts.max_line_length.max(self.line_pos);
+        counts
     }
-    let len = count_digits(num_bytes);
-    Ok((filename, counts, len))
 }
 
-const fn count_digits(n: usize) -> usize {
-    if n < 10 {
+/// Returns whether `bytes` is the start of a valid multibyte sequence that
+/// has been cut short.
+fn is_incomplete(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && str::from_utf8(bytes).is_err_and(|e| e.error_len().is_none())
+}
+
+/// Returns the number of columns `c` takes on a terminal. Unlike glibc,
+/// `unicode-width` gives the soft hyphen no width.
+fn char_width(c: char) -> usize {
+    if c == '\u{ad}' {
         1
-    } else if n < 100 {
-        2
-    } else if n < 1000 {
-        3
-    } else if n < 10_000 {
-        4
-    } else if n < 100_000 {
-        5
-    } else if n < 1_000_000 {
-        6
-    } else if n < 10_000_000 {
-        7
-    } else if n < 100_000_000 {
-        8
-    } else if n < 1_000_000_000 {
-        9
     } else {
-        10
+        c.width().unwrap_or(0)
     }
 }
 
-fn open(filename: &str) -> Result<Box<dyn BufRead>> {
-    match filename {
-        "-" => Ok(Box::new(BufReader::new(io::stdin()))),
-        _ => Ok(Box::new(BufReader::new(File::open(filename)?))),
+/// Returns whether `c` separates words: white space, including the no-break
+/// spaces.
+const fn is_space(c: char) -> bool {
+    c.is_whitespace() || c == '\u{2060}'
+}
+
+const fn count_digits(mut n: u64) -> usize {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
     }
+    digits
 }
 
-// arg FILE has a default value so cannot panic.
-#[allow(clippy::missing_panics_doc)]
+#[allow(clippy::too_many_lines)]
 #[must_use]
 pub fn get_args() -> Config {
     let matches = Command::new("wc")
@@ -166,7 +562,6 @@ pub fn get_args() -> Config {
             Arg::new("files")
                 .value_name("FILES")
                 .help("Input file(s)")
-                .default_value("-")
                 .num_args(1..),
         )
         .arg(
@@ -201,50 +596,106 @@ pub fn get_args() -> Config {
                 .action(ArgAction::SetTrue)
                 .help("print the word counts"),
         )
+        .arg(
+            Arg::new("files0_from")
+                .long("files0-from")
+                .value_name("F")
+                .conflicts_with("files")
+                .help("read input from the NUL-terminated file names in F (- for stdin)"),
+        )
+        .arg(
+            Arg::new("total")
+                .long("total")
+                .value_name("WHEN")
+                .value_parser(value_parser!(Total))
+                .default_value("auto")
+                .help("when to print a line with total counts"),
+        )
+        .arg(
+            Arg::new("max_line_length")
+                .short('L')
+                .long("max-line-length")
+                .action(ArgAction::SetTrue)
+                .help("print the maximum display width"),
+        )
         .get_matches();
 
     let files: Vec<String> = matches
         .get_many("files")
-        .expect("arg FILE missing")
+        .unwrap_or_default()
         .cloned()
         .collect();
 
     let mut lines = matches.get_flag("lines");
     let mut words = matches.get_flag("words");
+    let chars = matches.get_flag("chars");
     let mut bytes = matches.get_flag("bytes");
-    let mut chars = matches.get_flag("chars");
+    let max_line_length = matches.get_flag("max_line_length");
 
-    if [lines, words, bytes, chars].iter().all(|v| v == &false) {
-        (lines, words, bytes, chars) = (true, true, true, false);
+    if [lines, words, chars, bytes, max_line_length]
+        .iter()
+        .all(|v| v == &false)
+    {
+        (lines, words, bytes) = (true, true, true);
     }
 
     Config {
         files,
+        files0_from: matches.get_one("files0_from").cloned(),
         lines,
         words,
-        bytes,
         chars,
+        bytes,
+        max_line_length,
+        total: matches.get_one("total").copied().unwrap_or(Total::Auto),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Cursor;
+    use std::io::{BufReader, Cursor};
 
     #[test]
     fn test_count() {
         let config = Config {
             files: vec!["test.txt".to_owned()],
+            files0_from: None,
             lines: true,
             words: true,
             chars: false,
             bytes: true,
+            max_line_length: false,
+            total: Total::Auto,
         };
         let text = "I don't want the world. I just want your half.\r\n";
-        let info = count(Cursor::new(text), &config.files[0], &config);
-        assert!(info.is_ok());
-        let expected = ("test.txt", vec![1, 10, 48], 2);
-        assert_eq!(info.expect("failed to read file"), expected);
+        let mut counts = Counts::default();
+        assert!(count(Cursor::new(text), &config, &mut counts).is_ok());
+        assert_eq!(counts.columns(&config), vec![1, 10, 48]);
+        assert_eq!(counts.max_line_length, 46);
+    }
+
+    #[test]
+    fn test_count_across_buffers() {
+        let config = Config {
+            files: vec![],
+            files0_from: None,
+            lines: true,
+            words: true,
+            chars: true,
+            bytes: true,
+            max_line_length: true,
+            total: Total::Auto,
+        };
+        let text = b"caf\xc3\xa9 \xe6\x97\xa5\xe6\x9c\xac\t\xf0\x9f\x98\x80x\n\xe2\x82 \xc3\xff\xe2\x81\xa0y\xc3";
+        let mut expected = Counts::default();
+        assert!(count(Cursor::new(text), &config, &mut expected).is_ok());
+        assert_eq!(expected.columns(&config), vec![1, 4, 14, 29, 19]);
+        for capacity in 1..=8 {
+            let mut counts = Counts::default();
+            let file = BufReader::with_capacity(capacity, Cursor::new(text));
+            assert!(count(file, &config, &mut counts).is_ok());
+            assert_eq!(counts, expected, "buffer of {capacity} bytes");
+        }
     }
 }
@@ -1,6 +1,9 @@
 fn main() {
-    if let Err(e) = wc::run(&wc::get_args()) {
-        eprintln!("{e}");
-        std::process::exit(1);
+    match wc::run(&wc::get_args()) {
+        Ok(status) => std::process::exit(status),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
     }
 }
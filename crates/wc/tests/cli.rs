@@ -1,7 +1,8 @@
-use std::fs;
+use std::fs::{self, File};
+use std::process;
 
 use anyhow::Result;
-use assert_cmd::Command;
+use assert_cmd::{cargo::CommandCargoExt, Command};
 use predicates::prelude::*;
 use pretty_assertions::assert_eq;
 use rand::{distributions::Alphanumeric, Rng};
@@ -10,6 +11,8 @@ const PRG: &str = "wc";
 const EMPTY: &str = "tests/inputs/empty.txt";
 const FOX: &str = "tests/inputs/fox.txt";
 const ATLAMAL: &str = "tests/inputs/atlamal.txt";
+const WIDE: &str = "tests/inputs/wide.txt";
+const MIXED: &str = "tests/inputs/mixed.bin";
 
 fn gen_bad_file() -> String {
     loop {
@@ -43,11 +46,37 @@ fn skips_bad_file() -> Result<()> {
     Command::cargo_bin(PRG)?
         .arg(bad)
         .assert()
-        .success()
+        .failure()
         .stderr(predicate::str::is_match(expected)?);
     Ok(())
 }
 
+#[test]
+fn skips_bad_file_and_counts_others() -> Result<()> {
+    let bad = gen_bad_file();
+    let expected = fs::read_to_string("tests/expected/fox.txt.out")?;
+    Command::cargo_bin(PRG)?
+        .args([&bad, FOX])
+        .assert()
+        .failure()
+        .stderr(format!("wc: {bad}: No such file or directory\n"))
+        .stdout(predicate::str::starts_with(expected));
+    Ok(())
+}
+
+#[test]
+fn directory() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/dir.out")?;
+    let expected_err = fs::read_to_string("tests/expected/dir.err")?;
+    Command::cargo_bin(PRG)?
+        .arg("tests/inputs")
+        .assert()
+        .failure()
+        .stdout(expected)
+        .stderr(expected_err);
+    Ok(())
+}
+
 #[test]
 fn empty() -> Result<()> {
     run(&[EMPTY], "tests/expected/empty.txt.out")
@@ -93,6 +122,31 @@ fn fox_bytes_lines() -> Result<()> {
     run(&["-l", "-c", FOX], "tests/expected/fox.txt.cl.out")
 }
 
+#[test]
+fn fox_chars_bytes() -> Result<()> {
+    run(&["-cm", FOX], "tests/expected/fox.txt.cm.out")
+}
+
+#[test]
+fn fox_lines_words_chars() -> Result<()> {
+    run(&["-lwm", FOX], "tests/expected/fox.txt.lwm.out")
+}
+
+#[test]
+fn fox_words_chars() -> Result<()> {
+    run(&["-wm", FOX], "tests/expected/fox.txt.wm.out")
+}
+
+#[test]
+fn fox_lines_chars() -> Result<()> {
+    run(&["-ml", FOX], "tests/expected/fox.txt.ml.out")
+}
+
+#[test]
+fn fox_max_line_length() -> Result<()> {
+    run(&["--max-line-length", FOX], "tests/expected/fox.txt.L.out")
+}
+
 #[test]
 fn atlamal() -> Result<()> {
     run(&[ATLAMAL], "tests/expected/atlamal.txt.out")
@@ -128,11 +182,91 @@ fn atlamal_bytes_lines() -> Result<()> {
     run(&["-l", "-c", ATLAMAL], "tests/expected/atlamal.txt.cl.out")
 }
 
+#[test]
+fn atlamal_chars() -> Result<()> {
+    run(&["-m", ATLAMAL], "tests/expected/atlamal.txt.m.out")
+}
+
+#[test]
+fn atlamal_chars_bytes() -> Result<()> {
+    run(&["-cm", ATLAMAL], "tests/expected/atlamal.txt.cm.out")
+}
+
+#[test]
+fn atlamal_lines_words_chars() -> Result<()> {
+    run(&["-lwm", ATLAMAL], "tests/expected/atlamal.txt.lwm.out")
+}
+
+#[test]
+fn atlamal_words_chars() -> Result<()> {
+    run(&["-wm", ATLAMAL], "tests/expected/atlamal.txt.wm.out")
+}
+
+#[test]
+fn atlamal_lines_chars() -> Result<()> {
+    run(&["-ml", ATLAMAL], "tests/expected/atlamal.txt.ml.out")
+}
+
+#[test]
+fn atlamal_max_line_length() -> Result<()> {
+    run(&["-L", ATLAMAL], "tests/expected/atlamal.txt.L.out")
+}
+
+#[test]
+fn empty_max_line_length() -> Result<()> {
+    run(&["-L", EMPTY], "tests/expected/empty.txt.L.out")
+}
+
+#[test]
+fn wide_max_line_length() -> Result<()> {
+    run(&["-L", WIDE], "tests/expected/wide.txt.L.out")
+}
+
+#[test]
+fn wide_lines_max_line_length() -> Result<()> {
+    run(&["-lL", WIDE], "tests/expected/wide.txt.lL.out")
+}
+
+#[test]
+fn mixed() -> Result<()> {
+    run(&[MIXED], "tests/expected/mixed.bin.out")
+}
+
+#[test]
+fn mixed_chars() -> Result<()> {
+    run(&["-m", MIXED], "tests/expected/mixed.bin.m.out")
+}
+
+#[test]
+fn mixed_words() -> Result<()> {
+    run(&["-w", MIXED], "tests/expected/mixed.bin.w.out")
+}
+
+#[test]
+fn mixed_all_counts() -> Result<()> {
+    run(&["-lwmcL", MIXED], "tests/expected/mixed.bin.lwmcL.out")
+}
+
 #[test]
 fn atlamal_stdin() -> Result<()> {
-    let input = fs::read_to_string(ATLAMAL)?;
     let expected = fs::read_to_string("tests/expected/atlamal.txt.stdin.out")?;
 
+    let output = process::Command::cargo_bin(PRG)?
+        .stdin(File::open(ATLAMAL)?)
+        .output()
+        .expect("fail");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
+    assert_eq!(stdout, expected);
+    Ok(())
+}
+
+#[test]
+fn atlamal_pipe() -> Result<()> {
+    let input = fs::read_to_string(ATLAMAL)?;
+    let expected = fs::read_to_string("tests/expected/atlamal.txt.pipe.out")?;
+
     let output = Command::cargo_bin(PRG)?
         .write_stdin(input)
         .output()
@@ -144,6 +278,22 @@ fn atlamal_stdin() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn fox_stdin_file_all_counts() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/fox.txt.stdin.lwmcL.out")?;
+
+    let output = process::Command::cargo_bin(PRG)?
+        .args(["-lwmcL", "-"])
+        .stdin(File::open(FOX)?)
+        .output()
+        .expect("fail");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
+    assert_eq!(stdout, expected);
+    Ok(())
+}
+
 #[test]
 fn test_all() -> Result<()> {
     run(&[EMPTY, FOX, ATLAMAL], "tests/expected/all.out")
@@ -178,3 +328,198 @@ fn test_all_words_lines() -> Result<()> {
 fn test_all_bytes_lines() -> Result<()> {
     run(&["-cl", EMPTY, FOX, ATLAMAL], "tests/expected/all.cl.out")
 }
+
+#[test]
+fn test_all_max_line_length() -> Result<()> {
+    run(&["-L", EMPTY, FOX, ATLAMAL], "tests/expected/all.L.out")
+}
+
+#[test]
+fn test_all_lines_max_line_length() -> Result<()> {
+    run(&["-lL", EMPTY, FOX, ATLAMAL], "tests/expected/all.lL.out")
+}
+
+#[test]
+fn test_all_chars() -> Result<()> {
+    run(&["-m", EMPTY, FOX, ATLAMAL], "tests/expected/all.m.out")
+}
+
+#[test]
+fn test_all_chars_bytes() -> Result<()> {
+    run(&["-cm", EMPTY, FOX, ATLAMAL], "tests/expected/all.cm.out")
+}
+
+#[test]
+fn test_all_lines_words_chars() -> Result<()> {
+    run(&["-lwm", EMPTY, FOX, ATLAMAL], "tests/expected/all.lwm.out")
+}
+
+#[test]
+fn test_all_words_chars() -> Result<()> {
+    run(&["-wm", EMPTY, FOX, ATLAMAL], "tests/expected/all.wm.out")
+}
+
+#[test]
+fn test_all_lines_chars() -> Result<()> {
+    run(&["-ml", EMPTY, FOX, ATLAMAL], "tests/expected/all.ml.out")
+}
+
+#[test]
+fn test_all_counts() -> Result<()> {
+    run(
+        &["-lwmcL", EMPTY, FOX, ATLAMAL],
+        "tests/expected/all.lwmcL.out",
+    )
+}
+
+#[test]
+fn files0_from() -> Result<()> {
+    run(
+        &["--files0-from=tests/inputs/files0.txt"],
+        "tests/expected/files0.out",
+    )
+}
+
+#[test]
+fn files0_from_non_utf8_name() -> Result<()> {
+    let expected = fs::read("tests/expected/files0-latin1.out")?;
+    Command::cargo_bin(PRG)?
+        .arg("--files0-from=tests/inputs/files0-latin1.txt")
+        .assert()
+        .success()
+        .stdout(expected)
+        .stderr("");
+    Ok(())
+}
+
+#[test]
+fn files0_from_stdin() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/files0.stdin.out")?;
+
+    let output = process::Command::cargo_bin(PRG)?
+        .arg("--files0-from=-")
+        .stdin(File::open("tests/inputs/files0.txt")?)
+        .output()
+        .expect("fail");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).expect("invalid UTF-8");
+    assert_eq!(stdout, expected);
+    Ok(())
+}
+
+#[test]
+fn files0_from_pipe() -> Result<()> {
+    let input = fs::read("tests/inputs/files0.txt")?;
+    let expected = fs::read_to_string("tests/expected/files0.pipe.out")?;
+    Command::cargo_bin(PRG)?
+        .arg("--files0-from=-")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(expected);
+    Ok(())
+}
+
+#[test]
+fn files0_from_invalid_names() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/files0-bad.out")?;
+    let expected_err = fs::read_to_string("tests/expected/files0-bad.err")?;
+
+    let output = process::Command::cargo_bin(PRG)?
+        .arg("--files0-from=-")
+        .stdin(File::open("tests/inputs/files0-bad.txt")?)
+        .output()
+        .expect("fail");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stdout)?, expected);
+    assert_eq!(String::from_utf8(output.stderr)?, expected_err);
+    Ok(())
+}
+
+#[test]
+fn files0_from_empty_list() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .arg("--files0-from=-")
+        .write_stdin("")
+        .assert()
+        .success()
+        .stdout("");
+    Ok(())
+}
+
+#[test]
+fn dies_files0_from_bad_file() -> Result<()> {
+    let bad = gen_bad_file();
+    Command::cargo_bin(PRG)?
+        .arg(format!("--files0-from={bad}"))
+        .assert()
+        .failure()
+        .stderr(format!(
+            "wc: cannot open '{bad}' for reading: No such file or directory\n"
+        ));
+    Ok(())
+}
+
+#[test]
+fn dies_files0_from_with_operands() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .args(["--files0-from=tests/inputs/files0.txt", FOX])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+    Ok(())
+}
+
+#[test]
+fn total_always() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/fox.txt.out")?;
+    Command::cargo_bin(PRG)?
+        .args(["--total=always", FOX])
+        .assert()
+        .success()
+        .stdout(format!("{expected} 1  9 48 total\n"));
+    Ok(())
+}
+
+#[test]
+fn total_only() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/all.out")?;
+    let total = expected.lines().last().expect("no total line");
+    Command::cargo_bin(PRG)?
+        .args(["--total=only", EMPTY, FOX, ATLAMAL])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", total.trim_end_matches(" total")));
+    Ok(())
+}
+
+#[test]
+fn total_never() -> Result<()> {
+    let expected = fs::read_to_string("tests/expected/all.out")?;
+    let without_total: String = expected
+        .split_inclusive('\n')
+        .filter(|line| !line.ends_with(" total\n"))
+        .collect();
+    Command::cargo_bin(PRG)?
+        .args(["--total=never", EMPTY, FOX, ATLAMAL])
+        .assert()
+        .success()
+        .stdout(without_total);
+    Ok(())
+}
+
+#[test]
+fn total_auto() -> Result<()> {
+    run(&["--total=auto", FOX], "tests/expected/fox.txt.out")
+}
+
+#[test]
+fn dies_bad_total() -> Result<()> {
+    Command::cargo_bin(PRG)?
+        .arg("--total=sometimes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value 'sometimes'"));
+    Ok(())
+}